@@ -0,0 +1,279 @@
+use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+use syn::Error;
+use syn::FnArg;
+use syn::Ident;
+use syn::ImplItem;
+use syn::ImplItemMethod;
+use syn::ItemFn;
+use syn::ItemImpl;
+use syn::Result;
+use syn::ReturnType;
+use syn::Type;
+
+use crate::prefix::is_route_prefix;
+use crate::spec::parse_prefix;
+use crate::spec::Prefix;
+use crate::spec::Spec;
+use crate::structure::expand_impl;
+use crate::structure::expand_struct;
+use crate::suggest::did_you_mean;
+
+/// A method annotated with `#[route]`.
+struct Method {
+    name: Ident,
+    param: Ident,
+    params: Vec<Ident>,
+    req: Type,
+    output: Type,
+}
+
+pub(crate) fn controller(args: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    if !args.is_empty() {
+        return Err(Error::new(args.span(), "unexpected argument"));
+    }
+
+    let mut item = syn::parse2::<ItemImpl>(item)?;
+
+    if let Some((_, path, _)) = item.trait_ {
+        return Err(Error::new(
+            path.span(),
+            "controller cannot be a trait implementation",
+        ));
+    }
+
+    // The param struct of every route is generated outside of the impl block,
+    // where its generics are not in scope.
+    if !item.generics.params.is_empty() {
+        return Err(Error::new(
+            item.generics.span(),
+            "controller cannot have generic parameters",
+        ));
+    }
+
+    let type_name = match item.self_ty.as_ref() {
+        Type::Path(ty) => match ty.path.segments.last() {
+            Some(segment) => segment.ident.clone(),
+            None => return Err(Error::new(ty.span(), "unsupported controller type")),
+        },
+        ty => return Err(Error::new(ty.span(), "unsupported controller type")),
+    };
+
+    // `#[route_prefix]` placed after `#[controller]` is applied here, since
+    // the route attributes are gone by the time it runs.
+    let prefix = match item.attrs.iter().position(is_route_prefix) {
+        None => Prefix::default(),
+        Some(index) => item.attrs.remove(index).parse_args_with(parse_prefix)?,
+    };
+
+    let mut methods = Vec::new();
+    let mut structs = Vec::new();
+    for impl_item in item.items.iter_mut() {
+        let method = match impl_item {
+            ImplItem::Method(method) => method,
+            _ => continue,
+        };
+
+        let mut spec = match take_spec(method)? {
+            Some(spec) => spec,
+            None => continue,
+        };
+        spec.prefix(&prefix)?;
+
+        let (method, struct_) = expand_method(&type_name, method, spec)?;
+        methods.push(method);
+        structs.push(struct_);
+    }
+
+    let first = match methods.first() {
+        Some(first) => first,
+        None => {
+            return Err(Error::new(
+                item.self_ty.span(),
+                "controller does not have any route",
+            ))
+        }
+    };
+
+    let req_typ = &first.req;
+    let output = &first.output;
+    let res_typ = quote::quote!(<#output as ::tackt::__private::ResultParts>::Ok);
+    let err_typ = quote::quote!(<#output as ::tackt::__private::ResultParts>::Err);
+
+    let this_var: Ident = syn::parse_quote!(__this);
+    let req_var: Ident = syn::parse_quote!(__req);
+    let param_var: Ident = syn::parse_quote!(__param);
+
+    let routes = methods.iter().map(|method| {
+        let name = &method.name;
+        let param = &method.param;
+        let req = &method.req;
+        let args = method
+            .params
+            .iter()
+            .map(|name| quote::quote!(#param_var.#name));
+        quote::quote_spanned! {name.span()=>
+            {
+                let #this_var = #this_var.clone();
+                move |#req_var: #req, #param_var: #param| {
+                    let #this_var = #this_var.clone();
+                    async move { #this_var.#name(#req_var, #(#args),*).await }
+                }
+            }
+        }
+    });
+
+    let mut routes = routes.into_iter();
+    let first = routes.next();
+
+    let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+    let self_ty = &item.self_ty;
+
+    Ok(quote::quote! {
+        #(#structs)*
+
+        #item
+
+        impl #impl_generics #self_ty #where_clause {
+            /// Create a router from the routes of this controller.
+            ///
+            /// The controller is shared between the routes with an `Arc`.
+            #[allow(dead_code)]
+            pub fn router(self) -> ::tackt::Router<
+                impl ::tackt::Route<#req_typ, Response = #res_typ, Error = #err_typ>
+                    + ::std::clone::Clone
+            > {
+                let #this_var = ::std::sync::Arc::new(self);
+                ::tackt::Router::new(#first)
+                    #(.route(#routes))*
+            }
+        }
+    })
+}
+
+/// Remove the route attribute of a method and parse its spec.
+fn take_spec(method: &mut ImplItemMethod) -> Result<Option<Spec>> {
+    for (index, attr) in method.attrs.iter().enumerate() {
+        if let Some(spec) = Spec::from_attr(attr)? {
+            method.attrs.remove(index);
+            return Ok(Some(spec));
+        }
+    }
+    Ok(None)
+}
+
+fn expand_method(
+    type_name: &Ident,
+    method: &ImplItemMethod,
+    spec: Spec,
+) -> Result<(Method, TokenStream)> {
+    let inputs = &method.sig.inputs;
+
+    match inputs.first() {
+        Some(FnArg::Receiver(receiver))
+            if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+        _ => {
+            return Err(Error::new(
+                method.sig.span(),
+                "route method must take `&self` as the first argument",
+            ))
+        }
+    }
+
+    if inputs.len() != spec.num_param() + 2 {
+        return Err(Error::new(
+            method.sig.paren_token.span,
+            "route method must take `&self`, the request, and the route params",
+        ));
+    }
+
+    let req = match &inputs[1] {
+        FnArg::Typed(arg) => arg.ty.as_ref().clone(),
+        FnArg::Receiver(arg) => return Err(Error::new(arg.span(), "unexpected receiver")),
+    };
+
+    let mut params = Vec::with_capacity(spec.num_param());
+    for arg in inputs.iter().skip(2) {
+        match arg {
+            FnArg::Typed(arg) => match arg.pat.as_ref() {
+                syn::Pat::Ident(pat) => params.push(pat.ident.clone()),
+                _ => return Err(Error::new(arg.pat.span(), "argument must be an identifier")),
+            },
+            FnArg::Receiver(arg) => {
+                return Err(Error::new(arg.span(), "receiver cannot become route param"))
+            }
+        }
+    }
+
+    for param in spec.iter_param() {
+        if !params.contains(param) {
+            return Err(Error::new(
+                param.span(),
+                did_you_mean(
+                    format!(r#""{}" does not exists in function argument"#, param),
+                    param,
+                    &params,
+                ),
+            ));
+        }
+    }
+
+    let output = match parse_output(&method.sig.output) {
+        Some(output) => output,
+        None => {
+            return Err(Error::new(
+                method.sig.output.span(),
+                "route method must return a `Result`",
+            ))
+        }
+    };
+
+    let param = quote::format_ident!("__{}_{}", type_name, method.sig.ident);
+    let mut sig = method.sig.clone();
+    sig.ident = param.clone();
+    let item = ItemFn {
+        attrs: Vec::new(),
+        vis: method.vis.clone(),
+        sig,
+        block: Box::new(method.block.clone()),
+    };
+
+    let struct_ = expand_struct(&item, 2)?;
+    let impl_ = expand_impl(&struct_, &spec)?;
+
+    let method = Method {
+        name: method.sig.ident.clone(),
+        param,
+        params,
+        req,
+        output,
+    };
+
+    Ok((
+        method,
+        quote::quote! {
+            #struct_
+
+            #impl_
+        },
+    ))
+}
+
+/// Get the return type when it is a `Result`, possibly through an alias like
+/// `io::Result<T>`. Its types are resolved by the generated code.
+fn parse_output(output: &ReturnType) -> Option<Type> {
+    let ty = match output {
+        ReturnType::Type(_, ty) => ty.as_ref(),
+        ReturnType::Default => return None,
+    };
+
+    let segment = match ty {
+        Type::Path(ty) => ty.path.segments.last()?,
+        _ => return None,
+    };
+
+    match segment.ident == "Result" {
+        true => Some(ty.clone()),
+        false => None,
+    }
+}
@@ -1,11 +1,19 @@
 use proc_macro2::TokenStream;
+use syn::DeriveInput;
 use syn::Result;
 
+use crate::enumeration;
 use crate::spec::Spec;
 use crate::structure::expand_impl;
 use crate::structure::into_struct;
 
 pub(crate) fn derive(input: TokenStream) -> Result<TokenStream> {
+    let input = syn::parse2::<DeriveInput>(input)?;
+
+    if let syn::Data::Enum(ref data) = input.data {
+        return enumeration::expand_impl(&input.ident, &input.generics, data);
+    }
+
     let input = into_struct(input)?;
     let spec = Spec::from_attrs("route", input.attrs.as_slice())?;
 
@@ -0,0 +1,92 @@
+use proc_macro2::TokenStream;
+
+use syn::spanned::Spanned;
+use syn::DataEnum;
+use syn::Generics;
+use syn::Ident;
+use syn::Result;
+
+use crate::spec::Spec;
+use crate::structure::expand_ctor;
+use crate::structure::expand_matching;
+use crate::structure::expand_param_impl;
+use crate::structure::expand_priority;
+
+/// Expand `Param` implementation for an enum.
+///
+/// Every variant has its own route spec. Every variant is tried and ranked
+/// like `Or` does: the matching variant with the highest priority is returned,
+/// and ties go to the one declared first. When none matches, the errors are
+/// combined with `min`, like `Or` does, so `Error::Path` wins over
+/// `Error::Method`.
+pub(crate) fn expand_impl(
+    name: &Ident,
+    generics: &Generics,
+    data: &DataEnum,
+) -> Result<TokenStream> {
+    let err_typ: syn::Path = syn::parse_quote!(::tackt::Error);
+    let err_var: Ident = syn::parse_quote!(__err);
+
+    let mut variants = Vec::with_capacity(data.variants.len());
+    let mut priorities = Vec::with_capacity(data.variants.len());
+    for variant in data.variants.iter() {
+        let spec = Spec::from_attrs("route", variant.attrs.as_slice())?;
+        let variant_name = &variant.ident;
+
+        let ctor = expand_ctor(quote::quote!(#name::#variant_name), &variant.fields, &spec)?;
+        let matching = expand_matching(&spec, ctor);
+        variants.push(quote::quote_spanned! {variant.span()=>
+            (|| -> ::std::result::Result<Self, #err_typ> { #matching })()
+        });
+
+        let priority = expand_priority(&spec, &variant.fields);
+        priorities.push(quote::quote_spanned! {variant.span()=>
+            #name::#variant_name { .. } => #priority,
+        });
+    }
+
+    let best_var: Ident = syn::parse_quote!(__best);
+    let req_typ: Ident = syn::parse_quote!(__Req);
+
+    let mut variants = variants.into_iter();
+    let body = match variants.next() {
+        None => quote::quote! {
+            ::std::result::Result::Err(#err_typ::Path)
+        },
+        Some(first) => quote::quote! {
+            let #best_var: ::std::result::Result<Self, #err_typ> = #first;
+
+            // Variants are ranked and errors are combined like `Or` does.
+            #(
+                let #best_var = match (#best_var, #variants) {
+                    (::std::result::Result::Ok(best), ::std::result::Result::Ok(param)) => {
+                        let best_priority = ::tackt::Param::<#req_typ>::priority(&best);
+                        match ::tackt::Param::<#req_typ>::priority(&param) > best_priority {
+                            true => ::std::result::Result::Ok(param),
+                            false => ::std::result::Result::Ok(best),
+                        }
+                    }
+                    (::std::result::Result::Ok(best), ::std::result::Result::Err(_)) => {
+                        ::std::result::Result::Ok(best)
+                    }
+                    (::std::result::Result::Err(_), ::std::result::Result::Ok(param)) => {
+                        ::std::result::Result::Ok(param)
+                    }
+                    (::std::result::Result::Err(#err_var), ::std::result::Result::Err(err)) => {
+                        ::std::result::Result::Err(::std::cmp::min(#err_var, err))
+                    }
+                };
+            )*
+
+            #best_var
+        },
+    };
+
+    let priority = quote::quote! {
+        match *self {
+            #(#priorities)*
+        }
+    };
+
+    Ok(expand_param_impl(name, generics, body, priority))
+}
@@ -1,9 +1,13 @@
 use proc_macro::TokenStream;
 
+mod controller;
 mod derive;
+mod enumeration;
+mod prefix;
 mod route;
 mod spec;
 mod structure;
+mod suggest;
 
 #[proc_macro_derive(Param, attributes(route))]
 pub fn derive(input: TokenStream) -> TokenStream {
@@ -18,3 +22,41 @@ pub fn route(args: TokenStream, item: TokenStream) -> TokenStream {
         .unwrap_or_else(syn::Error::into_compile_error)
         .into()
 }
+
+#[proc_macro_attribute]
+pub fn controller(args: TokenStream, item: TokenStream) -> TokenStream {
+    controller::controller(args.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_attribute]
+pub fn route_prefix(args: TokenStream, item: TokenStream) -> TokenStream {
+    prefix::route_prefix(args.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+macro_rules! shorthand {
+    ($($name:ident: $method:literal),* $(,)?) => {
+        $(
+            #[proc_macro_attribute]
+            pub fn $name(args: TokenStream, item: TokenStream) -> TokenStream {
+                route::shorthand($method, args.into(), item.into())
+                    .unwrap_or_else(syn::Error::into_compile_error)
+                    .into()
+            }
+        )*
+    };
+}
+
+shorthand! {
+    get: "GET",
+    post: "POST",
+    put: "PUT",
+    delete: "DELETE",
+    patch: "PATCH",
+    head: "HEAD",
+    options: "OPTIONS",
+    trace: "TRACE",
+}
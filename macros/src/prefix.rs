@@ -0,0 +1,99 @@
+use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+use syn::Attribute;
+use syn::Error;
+use syn::ImplItem;
+use syn::Item;
+use syn::Result;
+
+use crate::spec::parse_prefix;
+use crate::spec::prefix_attr;
+use crate::spec::tackt_attr;
+use crate::spec::Prefix;
+
+/// Prepend a prefix to every route spec in a module or an `impl` block.
+///
+/// A nested module with its own `#[route_prefix]` gets this prefix prepended
+/// to its prefix instead.
+///
+/// The options of the prefix, like `ignore_case`, apply to every route that
+/// does not set them.
+pub(crate) fn route_prefix(args: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let prefix = syn::parse::Parser::parse2(parse_prefix, args)?;
+    let mut item = syn::parse2::<Item>(item)?;
+
+    match item {
+        Item::Mod(ref mut item) => match item.content {
+            Some((_, ref mut items)) => prefix_items(items, &prefix)?,
+            None => {
+                return Err(Error::new(
+                    item.span(),
+                    "route prefix cannot be applied to a module without body",
+                ))
+            }
+        },
+        Item::Impl(ref mut item) => prefix_impl_items(&mut item.items, &prefix)?,
+        _ => {
+            return Err(Error::new(
+                item.span(),
+                "route prefix can only be applied to a module or an impl block",
+            ))
+        }
+    }
+
+    Ok(quote::quote!(#item))
+}
+
+fn prefix_items(items: &mut [Item], prefix: &Prefix) -> Result<()> {
+    for item in items.iter_mut() {
+        match item {
+            Item::Fn(item) => prefix_attrs(&mut item.attrs, prefix)?,
+            Item::Impl(item) => prefix_impl_items(&mut item.items, prefix)?,
+            Item::Struct(item) => prefix_attrs(&mut item.attrs, prefix)?,
+            Item::Enum(item) => {
+                for variant in item.variants.iter_mut() {
+                    prefix_attrs(&mut variant.attrs, prefix)?;
+                }
+            }
+            Item::Mod(item) => {
+                let nested = item.attrs.iter_mut().find(|attr| is_route_prefix(attr));
+                match (nested, item.content.as_mut()) {
+                    (Some(attr), _) => {
+                        let mut nested = attr.parse_args_with(parse_prefix)?;
+                        nested.nest(prefix);
+                        attr.tokens = quote::quote!((#nested));
+                    }
+                    (None, Some((_, items))) => prefix_items(items, prefix)?,
+                    (None, None) => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn prefix_impl_items(items: &mut [ImplItem], prefix: &Prefix) -> Result<()> {
+    for item in items.iter_mut() {
+        if let ImplItem::Method(method) = item {
+            prefix_attrs(&mut method.attrs, prefix)?;
+        }
+    }
+    Ok(())
+}
+
+fn prefix_attrs(attrs: &mut [Attribute], prefix: &Prefix) -> Result<()> {
+    for attr in attrs.iter_mut() {
+        if prefix_attr(attr, prefix)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn is_route_prefix(attr: &Attribute) -> bool {
+    match tackt_attr(attr) {
+        Some(name) => name == "route_prefix",
+        None => false,
+    }
+}
@@ -8,9 +8,22 @@ use syn::Result;
 use crate::spec::Spec;
 use crate::structure::expand_impl;
 use crate::structure::expand_struct;
+use crate::suggest::did_you_mean;
 
 pub(crate) fn route(spec: TokenStream, item: TokenStream) -> Result<TokenStream> {
-    let spec = syn::parse2::<Spec>(spec)?;
+    expand(syn::parse2::<Spec>(spec)?, item)
+}
+
+/// `#[get(...)]` and other method shorthands of `#[route(GET: ...)]`.
+pub(crate) fn shorthand(
+    method: &str,
+    segments: TokenStream,
+    item: TokenStream,
+) -> Result<TokenStream> {
+    expand(Spec::shorthand(method, segments)?, item)
+}
+
+fn expand(spec: Spec, item: TokenStream) -> Result<TokenStream> {
     let item = syn::parse2::<ItemFn>(item)?;
 
     if item.sig.inputs.len() < spec.num_param() + 1 {
@@ -35,17 +48,29 @@ pub(crate) fn route(spec: TokenStream, item: TokenStream) -> Result<TokenStream>
             },
         })?;
 
-    for param in spec.iter_param() {
-        if !item.sig.inputs.iter().skip(skip).any(|arg| match arg {
+    let args: Vec<_> = item
+        .sig
+        .inputs
+        .iter()
+        .skip(skip)
+        .filter_map(|arg| match arg {
             FnArg::Typed(pat) => match pat.pat.as_ref() {
-                syn::Pat::Ident(pat) => &pat.ident == param,
-                _ => false,
+                syn::Pat::Ident(pat) => Some(&pat.ident),
+                _ => None,
             },
-            _ => false,
-        }) {
+            _ => None,
+        })
+        .collect();
+
+    for param in spec.iter_param() {
+        if !args.contains(&param) {
             return Err(Error::new(
                 param.span(),
-                format!(r#""{}" does not exists in function argument"#, param),
+                did_you_mean(
+                    format!(r#""{}" does not exists in function argument"#, param),
+                    param,
+                    args.iter().copied(),
+                ),
             ));
         }
     }
@@ -82,7 +107,7 @@ fn expand_fn(item: &ItemFn, spec: &Spec, skip: usize) -> TokenStream {
     quote::quote! {
         #(#attrs)*
         #vis #constness #asyncness #unsafety fn #name #generics (
-            #(#reserved_inputs)*,
+            #(#reserved_inputs,)*
             #name { #(#params),* }: #name,
         ) #output
         #where_
@@ -1,15 +1,21 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
 use syn::parse::Parse;
 use syn::parse::ParseStream;
 use syn::Attribute;
 use syn::Error;
 use syn::Ident;
+use syn::LitInt;
 use syn::LitStr;
 use syn::Result;
 use syn::Token;
 
+use crate::suggest::did_you_mean;
+
 pub(crate) struct Spec {
-    pub(crate) methods: Vec<Ident>,
+    pub(crate) methods: Vec<Method>,
     pub(crate) segments: Vec<Segment>,
+    pub(crate) options: Options,
 }
 
 impl Spec {
@@ -21,6 +27,45 @@ impl Spec {
             .unwrap_or_else(|| Ok(Spec::empty()))
     }
 
+    /// Parse the spec of a route attribute.
+    ///
+    /// A route attribute is either `#[route]` or a method shorthand like
+    /// `#[get]`. `None` is returned for any other attribute.
+    pub(crate) fn from_attr(attr: &Attribute) -> Result<Option<Self>> {
+        let method = match route_attr(attr) {
+            None => return Ok(None),
+            Some(None) => {
+                return match attr.tokens.is_empty() {
+                    true => Ok(Some(Spec::empty())),
+                    false => attr.parse_args().map(Some),
+                }
+            }
+            Some(Some(method)) => Method::Standard(method),
+        };
+
+        let (segments, options) = match attr.tokens.is_empty() {
+            true => (Vec::new(), Options::default()),
+            false => attr.parse_args_with(parse_shorthand)?,
+        };
+
+        Ok(Some(Spec::new(vec![method], segments, options)))
+    }
+
+    /// Parse the spec of a method shorthand attribute like `#[get]`.
+    pub(crate) fn shorthand(method: &str, args: TokenStream) -> Result<Self> {
+        let method = Method::Standard(Ident::new(method, proc_macro2::Span::call_site()));
+        let (segments, options) = syn::parse::Parser::parse2(parse_shorthand, args)?;
+        Ok(Spec::new(vec![method], segments, options))
+    }
+
+    /// Prepend the segments of `prefix` to the segments of this spec, and
+    /// take the options of `prefix` that are not set in this spec.
+    pub(crate) fn prefix(&mut self, prefix: &Prefix) -> Result<()> {
+        self.segments.splice(0..0, prefix.segments.iter().cloned());
+        self.options.inherit(&prefix.options);
+        check_params(&self.segments)
+    }
+
     pub(crate) fn iter_param(&self) -> impl DoubleEndedIterator<Item = &Ident> {
         self.segments.iter().filter_map(|segment| match segment {
             Segment::Lit(..) => None,
@@ -41,90 +86,635 @@ impl Parse for Spec {
             return Ok(Spec::empty());
         }
 
-        if input.peek(LitStr) || input.peek2(syn::Token![/]) {
-            return parse_segments(input).map(Spec::only_segments);
+        if is_option(input) {
+            return Ok(Spec::only_options(input.parse()?));
+        }
+
+        // A string literal is a method only when it starts a list of methods
+        // followed by the segments, so `"login", priority = 1` is a path.
+        let method_lit = input.peek(LitStr) && is_method_list(input);
+        if (input.peek(LitStr) && !method_lit) || input.peek2(syn::Token![/]) {
+            let segments = parse_segments(input)?;
+            let options = parse_trailing_options(input)?;
+            return Ok(Spec::new(Vec::new(), segments, options));
         }
 
         let methods = parse_methods(input)?;
 
-        if input.is_empty() {
-            return Ok(Spec::only_methods(methods));
+        if !input.peek(Token![:]) {
+            let options = parse_trailing_options(input)?;
+            return Ok(Spec::new(methods, Vec::new(), options));
         }
 
         input.parse::<syn::Token![:]>()?;
 
-        Ok(Spec::new(methods, parse_segments(input)?))
+        let segments = parse_segments(input)?;
+        let options = parse_trailing_options(input)?;
+        Ok(Spec::new(methods, segments, options))
     }
 }
 
 impl Spec {
-    const fn new(methods: Vec<Ident>, segments: Vec<Segment>) -> Self {
-        Spec { methods, segments }
+    const fn new(methods: Vec<Method>, segments: Vec<Segment>, options: Options) -> Self {
+        Spec {
+            methods,
+            segments,
+            options,
+        }
     }
 
     const fn empty() -> Self {
+        Spec::only_options(Options::new())
+    }
+
+    const fn only_options(options: Options) -> Self {
         Spec {
             methods: Vec::new(),
             segments: Vec::new(),
+            options,
         }
     }
+}
 
-    const fn only_methods(methods: Vec<Ident>) -> Self {
-        Spec {
-            methods,
-            segments: Vec::new(),
+/// Options that follow the segments, like `priority = 1`.
+#[derive(Clone, Default)]
+pub(crate) struct Options {
+    pub(crate) priority: Option<Level>,
+    pub(crate) case: Option<Case>,
+}
+
+/// Names of the options that take a value.
+const OPTIONS: [&str; 1] = ["priority"];
+
+/// Names of the options that do not take a value.
+const FLAGS: [&str; 2] = ["ignore_ascii_case", "ignore_case"];
+
+impl Options {
+    const fn new() -> Self {
+        Options {
+            priority: None,
+            case: None,
         }
     }
 
-    const fn only_segments(segments: Vec<Segment>) -> Self {
-        Spec {
-            segments,
-            methods: Vec::new(),
+    fn is_empty(&self) -> bool {
+        self.priority.is_none() && self.case.is_none()
+    }
+
+    /// Take the options of `other` that are not set in this one.
+    fn inherit(&mut self, other: &Options) {
+        if self.priority.is_none() {
+            self.priority = other.priority.clone();
+        }
+        if self.case.is_none() {
+            self.case = other.case.clone();
         }
     }
 }
 
-fn parse_methods(input: ParseStream) -> Result<Vec<Ident>> {
-    let mut methods = vec![input.parse()?];
+impl Parse for Options {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut options = Options::new();
+        let mut names: Vec<Ident> = Vec::new();
+
+        loop {
+            let name = input.parse::<Ident>()?;
+            if names.contains(&name) {
+                return Err(Error::new(
+                    name.span(),
+                    format!("duplicate option `{}`", name),
+                ));
+            }
+
+            if name == "priority" {
+                input.parse::<Token![=]>()?;
+                options.priority = Some(input.parse()?);
+            } else if name == "ignore_ascii_case" || name == "ignore_case" {
+                if let Some(case) = &options.case {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("`{}` cannot be combined with `{}`", name, case.ident()),
+                    ));
+                }
+                options.case = Some(match name == "ignore_case" {
+                    true => Case::Unicode(name.clone()),
+                    false => Case::Ascii(name.clone()),
+                });
+            } else {
+                let candidates: Vec<_> = OPTIONS
+                    .iter()
+                    .chain(FLAGS.iter())
+                    .map(|option| Ident::new(option, name.span()))
+                    .collect();
+                return Err(Error::new(
+                    name.span(),
+                    did_you_mean(format!("unknown option `{}`", name), &name, &candidates),
+                ));
+            }
+            names.push(name);
+
+            if input.is_empty() {
+                return Ok(options);
+            }
+            input.parse::<Token![,]>()?;
+        }
+    }
+}
+
+impl ToTokens for Options {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let priority = self
+            .priority
+            .as_ref()
+            .map(|priority| quote::quote!(priority = #priority));
+        let case = self
+            .case
+            .as_ref()
+            .map(|case| case.ident().to_token_stream());
+        let options = priority.into_iter().chain(case);
+        tokens.extend(quote::quote!(#(#options),*));
+    }
+}
+
+/// The level of `priority = N`, which may be negative.
+#[derive(Clone)]
+pub(crate) struct Level {
+    neg: Option<Token![-]>,
+    lit: LitInt,
+}
+
+impl Parse for Level {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let neg = input.parse()?;
+        let lit = input.parse::<LitInt>()?;
+        let level = Level { neg, lit };
+        match level.value() {
+            Ok(_) => Ok(level),
+            Err(_) => Err(Error::new(
+                level.lit.span(),
+                "priority must be an integer that fits in i32",
+            )),
+        }
+    }
+}
+
+impl Level {
+    pub(crate) fn value(&self) -> Result<i32> {
+        let value = self.lit.base10_parse::<i64>()?;
+        let value = match self.neg {
+            Some(_) => -value,
+            None => value,
+        };
+        i32::try_from(value).map_err(|err| Error::new(self.lit.span(), err))
+    }
+}
+
+impl ToTokens for Level {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.neg.to_tokens(tokens);
+        self.lit.to_tokens(tokens);
+    }
+}
+
+/// How literal segments ignore case.
+#[derive(Clone)]
+pub(crate) enum Case {
+    /// `ignore_ascii_case`
+    Ascii(Ident),
+    /// `ignore_case`, which compares the lowercase of the segments.
+    Unicode(Ident),
+}
+
+impl Case {
+    fn ident(&self) -> &Ident {
+        match self {
+            Case::Ascii(ident) => ident,
+            Case::Unicode(ident) => ident,
+        }
+    }
+}
+
+/// Wether the input starts with an option like `priority = 1` or
+/// `ignore_case`.
+fn is_option(input: ParseStream) -> bool {
+    let fork = input.fork();
+    let name = match fork.parse::<Ident>() {
+        Ok(name) => name,
+        Err(_) => return false,
+    };
+    if fork.peek(Token![=]) {
+        return true;
+    }
+    FLAGS.iter().any(|flag| name == flag) && (fork.is_empty() || fork.peek(Token![,]))
+}
+
+/// Wether the input starts with methods followed by `:`, or is only a list of
+/// methods. A single string literal without `:` is a path.
+fn is_method_list(input: ParseStream) -> bool {
+    let fork = input.fork();
+    let mut count = 0;
+    loop {
+        if fork.parse::<Method>().is_err() {
+            return false;
+        }
+        count += 1;
+        if fork.peek(Token![:]) {
+            return true;
+        }
+        if fork.is_empty() {
+            return count > 1;
+        }
+        if fork.parse::<Token![,]>().is_err() {
+            return false;
+        }
+    }
+}
+
+/// Parse the options after the segments or the methods, if there are any.
+fn parse_trailing_options(input: ParseStream) -> Result<Options> {
+    if input.is_empty() {
+        return Ok(Options::new());
+    }
+    input.parse::<Token![,]>()?;
+    input.parse()
+}
+
+/// Join `tokens` and `options` with a comma.
+fn with_options(tokens: TokenStream, options: &Options) -> TokenStream {
+    match (tokens.is_empty(), options.is_empty()) {
+        (_, true) => tokens,
+        (true, false) => quote::quote!(#options),
+        (false, false) => quote::quote!(#tokens, #options),
+    }
+}
+
+/// Attribute names of the method shorthands and their methods.
+pub(crate) const SHORTHANDS: [(&str, &str); 8] = [
+    ("get", "GET"),
+    ("post", "POST"),
+    ("put", "PUT"),
+    ("delete", "DELETE"),
+    ("patch", "PATCH"),
+    ("head", "HEAD"),
+    ("options", "OPTIONS"),
+    ("trace", "TRACE"),
+];
+
+/// The name of `attr` when it is a bare ident like `#[get]`, or a path into
+/// tackt like `#[tackt::get]`. Attributes of other crates, like
+/// `#[other::get]`, are `None`.
+pub(crate) fn tackt_attr(attr: &Attribute) -> Option<&Ident> {
+    let segments = &attr.path.segments;
+    match segments.len() {
+        1 if attr.path.leading_colon.is_none() => Some(&segments[0].ident),
+        2 if segments[0].ident == "tackt" => Some(&segments[1].ident),
+        _ => None,
+    }
+}
+
+/// Check wether `attr` is a route attribute.
+///
+/// `Some(None)` is returned for `#[route]`, and `Some(Some(method))` for a
+/// method shorthand.
+fn route_attr(attr: &Attribute) -> Option<Option<Ident>> {
+    let name = tackt_attr(attr)?;
+    if name == "route" {
+        return Some(None);
+    }
+    SHORTHANDS
+        .iter()
+        .find(|(shorthand, _)| name == shorthand)
+        .map(|(_, method)| Some(Ident::new(method, name.span())))
+}
+
+/// Rewrite a route attribute so its spec starts with `prefix`.
+///
+/// Returns `false` if `attr` is not a route attribute.
+pub(crate) fn prefix_attr(attr: &mut Attribute, prefix: &Prefix) -> Result<bool> {
+    let shorthand = match route_attr(attr) {
+        None => return Ok(false),
+        Some(method) => method.is_some(),
+    };
+
+    let mut spec = match Spec::from_attr(attr)? {
+        None => return Ok(false),
+        Some(spec) => spec,
+    };
+    spec.prefix(prefix)?;
+
+    attr.tokens = match shorthand {
+        true => {
+            let segments = Segments(&spec.segments).into_token_stream();
+            let args = with_options(segments, &spec.options);
+            quote::quote!((#args))
+        }
+        false => quote::quote!((#spec)),
+    };
+
+    Ok(true)
+}
+
+/// The argument of `#[route_prefix]`.
+///
+/// The options are the defaults of the routes under the prefix.
+#[derive(Default)]
+pub(crate) struct Prefix {
+    pub(crate) segments: Vec<Segment>,
+    pub(crate) options: Options,
+}
+
+impl Prefix {
+    /// Prepend `outer` to this prefix.
+    pub(crate) fn nest(&mut self, outer: &Prefix) {
+        self.segments.splice(0..0, outer.segments.iter().cloned());
+        self.options.inherit(&outer.options);
+    }
+}
+
+impl ToTokens for Prefix {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let segments = Segments(&self.segments).into_token_stream();
+        tokens.extend(with_options(segments, &self.options));
+    }
+}
+
+/// Parse the route prefix, which only has literals and params, and the
+/// options.
+pub(crate) fn parse_prefix(input: ParseStream) -> Result<Prefix> {
+    let (segments, options) = parse_shorthand(input)?;
+    if let Some(Segment::Wild(name)) = segments.last() {
+        return Err(Error::new(
+            name.span(),
+            "wildcard (*) cannot be part of a prefix",
+        ));
+    }
+    Ok(Prefix { segments, options })
+}
+
+/// Parse the arguments of a method shorthand: optional segments followed by
+/// the options.
+fn parse_shorthand(input: ParseStream) -> Result<(Vec<Segment>, Options)> {
+    if input.is_empty() {
+        return Ok((Vec::new(), Options::new()));
+    }
+    if is_option(input) {
+        return Ok((Vec::new(), input.parse()?));
+    }
+    let segments = parse_segments(input)?;
+    let options = parse_trailing_options(input)?;
+    Ok((segments, options))
+}
+
+fn parse_methods(input: ParseStream) -> Result<Vec<Method>> {
+    let mut methods: Vec<Method> = vec![input.parse()?];
 
     while input.peek(Token![,]) {
+        // `GET, priority = 1`
+        let fork = input.fork();
+        fork.parse::<Token![,]>()?;
+        if is_option(&fork) {
+            break;
+        }
         input.parse::<Token![,]>()?;
-        methods.push(input.parse()?);
+        let method: Method = input.parse()?;
+        if methods.iter().any(|x| x.name() == method.name()) {
+            return Err(Error::new(
+                method.span(),
+                format!("duplicate method `{}`", method.name()),
+            ));
+        }
+        methods.push(method);
+    }
+
+    // `ANY` matches any method, just like a spec without methods.
+    if let Some(any) = methods.iter().find(|method| method.is_any()) {
+        if methods.len() > 1 {
+            return Err(Error::new(
+                any.span(),
+                "ANY cannot be combined with other methods",
+            ));
+        }
+        methods.clear();
     }
 
     Ok(methods)
 }
 
+/// Methods that are associated constants of `http::Method`.
+const STANDARD_METHODS: [&str; 9] = [
+    "GET", "POST", "PUT", "DELETE", "HEAD", "OPTIONS", "CONNECT", "PATCH", "TRACE",
+];
+
+/// A method in a spec.
+pub(crate) enum Method {
+    /// `GET`, `POST`, etc. and `ANY`.
+    Standard(Ident),
+    /// An extension method written as a string literal like `"PROPFIND"`.
+    Extension(LitStr),
+}
+
+impl Method {
+    fn is_any(&self) -> bool {
+        matches!(self, Method::Standard(ident) if ident == "ANY")
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Method::Standard(ident) => ident.to_string(),
+            Method::Extension(lit) => lit.value(),
+        }
+    }
+
+    fn span(&self) -> proc_macro2::Span {
+        match self {
+            Method::Standard(ident) => ident.span(),
+            Method::Extension(lit) => lit.span(),
+        }
+    }
+}
+
+impl Parse for Method {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(LitStr) {
+            let lit = input.parse::<LitStr>()?;
+            let value = lit.value();
+            if value.is_empty() || !value.bytes().all(is_token) {
+                return Err(Error::new(lit.span(), "invalid method"));
+            }
+            if STANDARD_METHODS.contains(&value.as_str()) {
+                return Ok(Method::Standard(Ident::new(&value, lit.span())));
+            }
+            return Ok(Method::Extension(lit));
+        }
+
+        let ident = input.parse::<Ident>()?;
+        if ident != "ANY" && !STANDARD_METHODS.iter().any(|method| ident == method) {
+            return Err(Error::new(
+                ident.span(),
+                format!(
+                    r#"unknown method "{ident}", an extension method must be a string literal: "{ident}""#
+                ),
+            ));
+        }
+        Ok(Method::Standard(ident))
+    }
+}
+
+impl ToTokens for Method {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Method::Standard(ident) => ident.to_tokens(tokens),
+            Method::Extension(lit) => lit.to_tokens(tokens),
+        }
+    }
+}
+
+/// Wether `byte` is a `tchar` of RFC 7230, which makes up a method.
+const fn is_token(byte: u8) -> bool {
+    matches!(
+        byte,
+        b'!' | b'#'
+            | b'$'
+            | b'%'
+            | b'&'
+            | b'\''
+            | b'*'
+            | b'+'
+            | b'-'
+            | b'.'
+            | b'^'
+            | b'_'
+            | b'`'
+            | b'|'
+            | b'~'
+            | b'0'..=b'9'
+            | b'a'..=b'z'
+            | b'A'..=b'Z'
+    )
+}
+
 fn parse_segments(input: ParseStream) -> Result<Vec<Segment>> {
-    let mut segments = vec![input.parse()?];
+    let mut segments = Vec::new();
+    input.parse::<Segment>()?.split_into(&mut segments);
 
     while input.peek(Token![/]) {
         input.parse::<Token![/]>()?;
-        let segment = input.parse()?;
+        let segment = input.parse::<Segment>()?;
 
-        if matches!(segment, Segment::Wild(..)) && !input.is_empty() {
+        if matches!(segment, Segment::Wild(..)) && !input.is_empty() && !input.peek(Token![,]) {
             return Err(Error::new(
                 input.span(),
                 "wildcard (*) must be the last segment",
             ));
         }
 
-        segments.push(segment);
+        segment.split_into(&mut segments);
     }
 
+    check_params(&segments)?;
     Ok(segments)
 }
 
+/// Check that every param name is used once.
+fn check_params(segments: &[Segment]) -> Result<()> {
+    let mut names: Vec<&Ident> = Vec::new();
+    for segment in segments {
+        let name = match segment {
+            Segment::Lit(..) => continue,
+            Segment::Param(name) => name,
+            Segment::Wild(name) => name,
+        };
+        if names.contains(&name) {
+            return Err(Error::new(
+                name.span(),
+                format!("duplicate param `{}`", name),
+            ));
+        }
+        names.push(name);
+    }
+    Ok(())
+}
+
+impl ToTokens for Spec {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let methods = &self.methods;
+        let segments = Segments(&self.segments);
+        let spec = match (self.methods.is_empty(), self.segments.is_empty()) {
+            (true, true) => TokenStream::new(),
+            (true, false) => quote::quote!(#segments),
+            (false, true) => quote::quote!(#(#methods),*),
+            (false, false) => quote::quote!(#(#methods),*: #segments),
+        };
+        tokens.extend(with_options(spec, &self.options));
+    }
+}
+
+struct Segments<'a>(&'a [Segment]);
+
+impl ToTokens for Segments<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let segments = self.0.iter();
+        tokens.extend(quote::quote!(#(#segments)/ *));
+    }
+}
+
+#[derive(Clone)]
 pub(crate) enum Segment {
     Lit(LitStr),
     Param(Ident),
     Wild(Ident),
 }
 
+impl Segment {
+    /// Push this segment to `segments`, splitting a literal like `"a/b"` into
+    /// `"a" / "b"`.
+    fn split_into(self, segments: &mut Vec<Segment>) {
+        match self {
+            Segment::Lit(lit) if lit.value().contains('/') => segments.extend(
+                lit.value()
+                    .split('/')
+                    .map(|part| Segment::Lit(LitStr::new(part, lit.span()))),
+            ),
+            segment => segments.push(segment),
+        }
+    }
+}
+
+impl ToTokens for Segment {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Segment::Lit(lit) => quote::quote!(#lit),
+            Segment::Param(name) => quote::quote!(#name),
+            Segment::Wild(name) => quote::quote!(#name *),
+        });
+    }
+}
+
 impl Parse for Segment {
     fn parse(input: ParseStream) -> Result<Self> {
         if input.peek(LitStr) {
-            return input.parse().map(Self::Lit);
+            let lit = input.parse::<LitStr>()?;
+            let value = lit.value();
+            if value.is_empty() {
+                return Err(Error::new(lit.span(), "literal segment cannot be empty"));
+            }
+            // `"a/b"` is split into `"a" / "b"` by `parse_segments`.
+            if value.split('/').any(str::is_empty) {
+                let parts: Vec<_> = value
+                    .split('/')
+                    .filter(|part| !part.is_empty())
+                    .map(|part| format!("{:?}", part))
+                    .collect();
+                let message = match parts.is_empty() {
+                    true => "literal segment cannot be empty".to_string(),
+                    false => format!(
+                        "literal segment cannot have an empty part, did you mean `{}`?",
+                        parts.join(" / ")
+                    ),
+                };
+                return Err(Error::new(lit.span(), message));
+            }
+            return Ok(Segment::Lit(lit));
         }
 
         let ident = input.parse()?;
@@ -215,3 +805,209 @@ fn method_and_path() {
     let segment = syn::parse2::<Spec>(stream);
     assert!(segment.is_ok());
 }
+
+#[test]
+#[cfg(test)]
+fn prefix() {
+    let prefix = syn::parse::Parser::parse2(parse_prefix, quote::quote!("api" / version));
+    let prefix = prefix.unwrap();
+
+    let mut spec = syn::parse2::<Spec>(quote::quote!(GET, PUT: "entity" / id / path*)).unwrap();
+    spec.prefix(&prefix).unwrap();
+    let tokens = quote::quote!(#spec).to_string();
+    let expected = quote::quote!(GET, PUT: "api" / version / "entity" / id / path*).to_string();
+    assert_eq!(tokens, expected);
+
+    let mut spec = syn::parse2::<Spec>(quote::quote!(GET)).unwrap();
+    spec.prefix(&prefix).unwrap();
+    let tokens = quote::quote!(#spec).to_string();
+    assert_eq!(tokens, quote::quote!(GET: "api" / version).to_string());
+
+    let prefix = syn::parse::Parser::parse2(parse_prefix, quote::quote!("api" / path*));
+    assert!(prefix.is_err());
+
+    let prefix = syn::parse::Parser::parse2(parse_prefix, quote::quote!("api")).unwrap();
+    let attrs: Vec<Attribute> = vec![
+        syn::parse_quote!(#[get("a")]),
+        syn::parse_quote!(#[tackt::get("a")]),
+        syn::parse_quote!(#[::tackt::route(GET: "a")]),
+    ];
+    for mut attr in attrs {
+        assert!(prefix_attr(&mut attr, &prefix).unwrap());
+    }
+    let attrs: Vec<Attribute> = vec![
+        syn::parse_quote!(#[other::get("a")]),
+        syn::parse_quote!(#[::get("a")]),
+        syn::parse_quote!(#[other::tackt::route]),
+    ];
+    for mut attr in attrs {
+        let tokens = attr.tokens.to_string();
+        assert!(!prefix_attr(&mut attr, &prefix).unwrap());
+        assert_eq!(attr.tokens.to_string(), tokens);
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn methods() {
+    let stream = syn::parse_quote!("PROPFIND", "MKCOL": "dav" / path*);
+    let spec = syn::parse2::<Spec>(stream).unwrap();
+    assert!(matches!(
+        spec.methods[..],
+        [Method::Extension(..), Method::Extension(..)]
+    ));
+    assert_eq!(spec.segments.len(), 2);
+
+    let stream = syn::parse_quote!("GET", PURGE);
+    let spec = syn::parse2::<Spec>(stream);
+    assert!(spec.is_err());
+
+    let stream = syn::parse_quote!("GET", "PURGE");
+    let spec = syn::parse2::<Spec>(stream).unwrap();
+    assert!(matches!(
+        spec.methods[..],
+        [Method::Standard(..), Method::Extension(..)]
+    ));
+
+    let stream = syn::parse_quote!("BAD METHOD": "path");
+    let spec = syn::parse2::<Spec>(stream);
+    assert!(spec.is_err());
+
+    let stream = syn::parse_quote!("path");
+    let spec = syn::parse2::<Spec>(stream).unwrap();
+    assert!(spec.methods.is_empty());
+    assert_eq!(spec.segments.len(), 1);
+
+    let stream = syn::parse_quote!(ANY: "path");
+    let spec = syn::parse2::<Spec>(stream).unwrap();
+    assert!(spec.methods.is_empty());
+
+    let stream = syn::parse_quote!(ANY, GET: "path");
+    let spec = syn::parse2::<Spec>(stream);
+    assert!(spec.is_err());
+}
+
+#[test]
+#[cfg(test)]
+fn validate() {
+    let invalid = [
+        quote::quote!(GET: "a" / id / id),
+        quote::quote!(GET: "a" / id / id*),
+        quote::quote!(GET, GET: "a"),
+        quote::quote!(GET, "GET": "a"),
+        quote::quote!("PURGE", "PURGE": "a"),
+        quote::quote!(GET: ""),
+        quote::quote!(GET: "a//b"),
+        quote::quote!(GET: "/a"),
+        quote::quote!("/"),
+    ];
+    for stream in invalid {
+        assert!(syn::parse2::<Spec>(stream).is_err());
+    }
+
+    let err = syn::parse2::<Spec>(quote::quote!("a/b/" / id))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        r#"literal segment cannot have an empty part, did you mean `"a" / "b"`?"#
+    );
+
+    let spec = syn::parse2::<Spec>(quote::quote!(GET: "api/v1" / id / "a/b")).unwrap();
+    let tokens = quote::quote!(#spec).to_string();
+    let expected = quote::quote!(GET: "api" / "v1" / id / "a" / "b").to_string();
+    assert_eq!(tokens, expected);
+
+    let prefix = syn::parse::Parser::parse2(parse_prefix, quote::quote!("api" / id)).unwrap();
+    let mut spec = syn::parse2::<Spec>(quote::quote!(GET: "user" / id)).unwrap();
+    assert!(spec.prefix(&prefix).is_err());
+}
+
+#[test]
+#[cfg(test)]
+fn options() {
+    let spec = syn::parse2::<Spec>(quote::quote!(GET: "a" / id, priority = 1)).unwrap();
+    assert_eq!(spec.options.priority.unwrap().value().unwrap(), 1);
+    assert_eq!(spec.segments.len(), 2);
+
+    let spec = syn::parse2::<Spec>(quote::quote!(GET, PUT, priority = -1)).unwrap();
+    assert_eq!(spec.options.priority.unwrap().value().unwrap(), -1);
+    assert_eq!(spec.methods.len(), 2);
+
+    let spec = syn::parse2::<Spec>(quote::quote!(priority = 2)).unwrap();
+    assert!(spec.methods.is_empty() && spec.segments.is_empty());
+    let tokens = quote::quote!(#spec).to_string();
+    assert_eq!(tokens, quote::quote!(priority = 2).to_string());
+
+    // a literal followed by an option is a path, not a method.
+    let spec = syn::parse2::<Spec>(quote::quote!("login", priority = 1)).unwrap();
+    assert!(spec.methods.is_empty());
+    assert_eq!(spec.segments.len(), 1);
+    assert_eq!(spec.options.priority.unwrap().value().unwrap(), 1);
+
+    let spec = syn::parse2::<Spec>(quote::quote!("login", ignore_case)).unwrap();
+    assert!(spec.methods.is_empty());
+    assert_eq!(spec.segments.len(), 1);
+    assert!(spec.options.case.is_some());
+
+    let spec = Spec::shorthand("GET", quote::quote!("a" / rest*, priority = 3)).unwrap();
+    assert_eq!(spec.options.priority.unwrap().value().unwrap(), 3);
+
+    let prefix = syn::parse::Parser::parse2(parse_prefix, quote::quote!("api")).unwrap();
+    let mut spec = syn::parse2::<Spec>(quote::quote!(GET: id, priority = 1)).unwrap();
+    spec.prefix(&prefix).unwrap();
+    let tokens = quote::quote!(#spec).to_string();
+    let expected = quote::quote!(GET: "api" / id, priority = 1).to_string();
+    assert_eq!(tokens, expected);
+
+    let invalid = [
+        quote::quote!(GET: "a", prio = 1),
+        quote::quote!(GET: "a", priority = 1, priority = 2),
+        quote::quote!(GET: "a", priority = "1"),
+        quote::quote!(GET: "a", priority = 9999999999),
+        quote::quote!(GET: "a",),
+    ];
+    for stream in invalid {
+        assert!(syn::parse2::<Spec>(stream).is_err());
+    }
+
+    let err = syn::parse2::<Spec>(quote::quote!(GET: "a", priorty = 1))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "unknown option `priorty`, did you mean `priority`?"
+    );
+}
+
+#[test]
+#[cfg(test)]
+fn case() {
+    let spec = syn::parse2::<Spec>(quote::quote!(GET: "a", ignore_case)).unwrap();
+    assert!(matches!(spec.options.case, Some(Case::Unicode(..))));
+
+    let spec = syn::parse2::<Spec>(quote::quote!(GET, ignore_ascii_case, priority = 1)).unwrap();
+    assert!(matches!(spec.options.case, Some(Case::Ascii(..))));
+    assert_eq!(spec.methods.len(), 1);
+
+    let spec = syn::parse2::<Spec>(quote::quote!(ignore_ascii_case)).unwrap();
+    assert!(spec.segments.is_empty());
+
+    // a param, not an option.
+    let spec = syn::parse2::<Spec>(quote::quote!(ignore_case / "a")).unwrap();
+    assert!(spec.options.case.is_none());
+    assert_eq!(spec.segments.len(), 2);
+
+    let spec = syn::parse2::<Spec>(quote::quote!(GET: "a", ignore_case = 1));
+    assert!(spec.is_err());
+    let spec = syn::parse2::<Spec>(quote::quote!(GET: "a", ignore_case, ignore_ascii_case));
+    assert!(spec.is_err());
+
+    let prefix = quote::quote!("api", ignore_ascii_case, priority = 1);
+    let prefix = syn::parse::Parser::parse2(parse_prefix, prefix).unwrap();
+    let mut spec = syn::parse2::<Spec>(quote::quote!(GET: id, priority = 2)).unwrap();
+    spec.prefix(&prefix).unwrap();
+    let tokens = quote::quote!(#spec).to_string();
+    let expected = quote::quote!(GET: "api" / id, priority = 2, ignore_ascii_case).to_string();
+    assert_eq!(tokens, expected);
+}
@@ -3,19 +3,25 @@ use proc_macro2::TokenStream;
 use syn::spanned::Spanned;
 use syn::DeriveInput;
 use syn::Error;
+use syn::Fields;
 use syn::FnArg;
+use syn::Generics;
 use syn::Ident;
 use syn::ItemFn;
 use syn::ItemStruct;
+use syn::LitByteStr;
+use syn::LitStr;
 use syn::Path;
 use syn::Result;
+use syn::Type;
 
+use crate::spec::Case;
+use crate::spec::Method;
 use crate::spec::Segment;
 use crate::spec::Spec;
+use crate::suggest::did_you_mean;
 
-pub(crate) fn into_struct(input: TokenStream) -> Result<ItemStruct> {
-    let input = syn::parse2::<DeriveInput>(input)?;
-
+pub(crate) fn into_struct(input: DeriveInput) -> Result<ItemStruct> {
     match input.data {
         syn::Data::Struct(data) => Ok(ItemStruct {
             attrs: input.attrs,
@@ -26,34 +32,210 @@ pub(crate) fn into_struct(input: TokenStream) -> Result<ItemStruct> {
             fields: data.fields,
             semi_token: data.semi_token,
         }),
-        _ => Err(Error::new(input.span(), "not a struct")),
+        _ => Err(Error::new(input.span(), "not a struct or an enum")),
     }
 }
 
 pub(crate) fn expand_impl(item: &ItemStruct, spec: &Spec) -> Result<TokenStream> {
-    let fields: Vec<_> = match item.fields {
-        syn::Fields::Named(ref fields) => fields
-            .named
-            .iter()
-            .filter_map(|field| field.ident.as_ref())
-            .collect(),
-        _ => {
-            return Err(Error::new(
-                item.fields.span(),
-                "not a struct with named fields",
-            ))
+    let struct_name = &item.ident;
+    let ctor = expand_ctor(quote::quote!(#struct_name), &item.fields, spec)?;
+    let matching = expand_matching(spec, ctor);
+    let priority = expand_priority(spec, &item.fields);
+
+    Ok(expand_param_impl(
+        struct_name,
+        &item.generics,
+        matching,
+        priority,
+    ))
+}
+
+/// The `Priority` of a param matched with `spec`.
+///
+/// A param is constrained unless its field is a `String` or a `Capture`. A
+/// path that does not end with a wildcard gets an `end`.
+pub(crate) fn expand_priority(spec: &Spec, fields: &Fields) -> TokenStream {
+    let mut index = 0;
+    let segments = spec.segments.iter().map(|segment| match segment {
+        Segment::Lit(..) => quote::quote!(.literal()),
+        Segment::Wild(..) => {
+            index += 1;
+            quote::quote!(.wildcard())
         }
+        Segment::Param(name) => {
+            let ty = match fields {
+                Fields::Named(fields) => fields
+                    .named
+                    .iter()
+                    .find(|field| field.ident.as_ref() == Some(name))
+                    .map(|field| &field.ty),
+                Fields::Unnamed(fields) => fields.unnamed.iter().nth(index).map(|field| &field.ty),
+                Fields::Unit => None,
+            };
+            index += 1;
+            match ty.is_some_and(is_constrained) {
+                true => quote::quote!(.constrained()),
+                false => quote::quote!(.param()),
+            }
+        }
+    });
+    let mut segments: Vec<_> = segments.collect();
+    if !matches!(spec.segments.last(), Some(Segment::Wild(..))) {
+        segments.push(quote::quote!(.end()));
+    }
+
+    let level = spec
+        .options
+        .priority
+        .as_ref()
+        .map(|level| quote::quote!(.with_level(#level)));
+
+    quote::quote! {
+        ::tackt::Priority::new() #(#segments)* #level
+    }
+}
+
+/// Wether parsing a segment into `ty` may fail.
+fn is_constrained(ty: &Type) -> bool {
+    let name = match ty {
+        Type::Path(ty) => match ty.path.segments.last() {
+            Some(segment) => &segment.ident,
+            None => return true,
+        },
+        _ => return true,
     };
+    name != "String" && name != "Capture"
+}
+
+/// Construct `path` from the params of `spec`.
+///
+/// Named fields are bound by name, tuple fields are bound in the order of the
+/// params in `spec`, and a unit has no params.
+pub(crate) fn expand_ctor(path: TokenStream, fields: &Fields, spec: &Spec) -> Result<TokenStream> {
+    match fields {
+        Fields::Named(fields) => {
+            let fields: Vec<_> = fields
+                .named
+                .iter()
+                .filter_map(|field| field.ident.as_ref())
+                .collect();
+
+            for field in fields.iter() {
+                if !spec.iter_param().any(|x| x == *field) {
+                    return Err(Error::new(
+                        field.span(),
+                        did_you_mean(
+                            format!(r#""{}" does not exists in route param"#, field),
+                            field,
+                            spec.iter_param(),
+                        ),
+                    ));
+                }
+            }
+
+            for param in spec.iter_param() {
+                if !fields.contains(&param) {
+                    return Err(Error::new(
+                        param.span(),
+                        did_you_mean(
+                            format!(r#""{}" does not exists in field"#, param),
+                            param,
+                            fields.iter().copied(),
+                        ),
+                    ));
+                }
+            }
+
+            Ok(quote::quote! {
+                #path {
+                    #(#fields),*
+                }
+            })
+        }
+        Fields::Unnamed(fields) => {
+            if fields.unnamed.len() != spec.num_param() {
+                return Err(Error::new(
+                    fields.span(),
+                    format!(
+                        "expected {} field(s) for route param, found {}",
+                        spec.num_param(),
+                        fields.unnamed.len()
+                    ),
+                ));
+            }
 
-    for field in fields.iter() {
-        if !spec.iter_param().any(|x| x == *field) {
-            return Err(Error::new(
-                field.span(),
-                format!(r#""{}" does not exists in route param"#, field),
-            ));
+            let params = spec.iter_param();
+            Ok(quote::quote! {
+                #path(#(#params),*)
+            })
         }
+        Fields::Unit => match spec.iter_param().next() {
+            Some(param) => Err(Error::new(
+                param.span(),
+                format!(r#""{}" does not exists in field"#, param),
+            )),
+            None => Ok(path),
+        },
     }
+}
 
+/// Wrap `body` into `Param` implementation.
+///
+/// `body` is the body of `from_request`. The request's path and method are
+/// bound to `__path` and `__method` before `body`.
+///
+/// The request type is named `__Req`, so it does not clash with the generics
+/// of the param.
+///
+/// `priority` is the body of `priority`.
+pub(crate) fn expand_param_impl(
+    name: &Ident,
+    generics: &Generics,
+    body: TokenStream,
+    priority: TokenStream,
+) -> TokenStream {
+    let err_typ: Path = syn::parse_quote!(::tackt::Error);
+
+    // local names
+    let method_var: Ident = syn::parse_quote!(__method);
+    let path_var: Ident = syn::parse_quote!(__path);
+    let req_var: Ident = syn::parse_quote!(__req);
+    let req_typ: Ident = syn::parse_quote!(__Req);
+
+    let mut impl_generics = generics.clone();
+    impl_generics
+        .params
+        .push(syn::parse_quote!(#req_typ: ::tackt::PathReq + ::tackt::MethodReq));
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote::quote! {
+        #[automatically_derived]
+        #[allow(unused_qualifications)]
+        impl #impl_generics ::tackt::Param<#req_typ> for #name #ty_generics
+        #where_clause
+        {
+            fn from_request(#req_var: &#req_typ) -> ::std::result::Result<Self, #err_typ> {
+                let #path_var = ::tackt::PathReq::path(#req_var);
+                let #method_var = ::tackt::MethodReq::method(#req_var);
+
+                #body
+            }
+
+            #[inline]
+            fn priority(&self) -> ::tackt::Priority {
+                #priority
+            }
+        }
+    }
+}
+
+/// Match the request's `__path` and `__method` against `spec`, then evaluate
+/// to `Ok(ctor)`. Any mismatch returns early with an error.
+///
+/// Params and wildcards are parsed with `FromSegment`, which gets `__req` so
+/// the segment can be captured without copying.
+pub(crate) fn expand_matching(spec: &Spec, ctor: TokenStream) -> TokenStream {
     // error
     let err_typ: Path = syn::parse_quote!(::tackt::Error);
     let err_404: Path = syn::parse_quote!(#err_typ::Path);
@@ -65,19 +247,69 @@ pub(crate) fn expand_impl(item: &ItemStruct, spec: &Spec) -> Result<TokenStream>
     let next_var: Ident = syn::parse_quote!(__next);
     let req_var: Ident = syn::parse_quote!(__req);
 
-    let segment_matching = spec.segments.iter().map(|segment| match segment {
-        Segment::Lit(lit) => quote::quote_spanned! {lit.span()=>
-            let (#next_var, #path_var) = #path_var.split_once('/').unwrap_or((#path_var, ""));
-            if #next_var != #lit {
-                return Err(#err_404);
+    let segment_matching = group_literals(&spec.segments).map(|group| match group {
+        // Consecutive literals are matched at once, so `"a" / "b"` costs a
+        // single `strip_prefix`.
+        Group::Lit(lits) => {
+            let span = lits[0].span();
+            let prefix = lits
+                .iter()
+                .map(|lit| lit.value())
+                .collect::<Vec<_>>()
+                .join("/");
+            match &spec.options.case {
+                None => {
+                    let prefix = LitStr::new(&prefix, span);
+                    quote::quote_spanned! {span=>
+                        let #path_var = match #path_var.strip_prefix(#prefix) {
+                            ::std::option::Option::Some("") => "",
+                            ::std::option::Option::Some(#next_var) => {
+                                #next_var.strip_prefix('/').ok_or(#err_404)?
+                            }
+                            ::std::option::Option::None => return Err(#err_404),
+                        };
+                    }
+                }
+                Some(Case::Ascii(..)) => {
+                    let len = prefix.len();
+                    let prefix = LitStr::new(&prefix, span);
+                    quote::quote_spanned! {span=>
+                        let #path_var = match #path_var.get(..#len) {
+                            ::std::option::Option::Some(#next_var)
+                                if #next_var.eq_ignore_ascii_case(#prefix) =>
+                            {
+                                match &#path_var[#len..] {
+                                    "" => "",
+                                    #next_var => #next_var.strip_prefix('/').ok_or(#err_404)?,
+                                }
+                            }
+                            _ => return Err(#err_404),
+                        };
+                    }
+                }
+                // The byte length may change with the case, so the segments
+                // are compared one by one.
+                Some(Case::Unicode(..)) => {
+                    let lits = lits.iter().map(|lit| {
+                        let lower: String = lit.value().chars().flat_map(char::to_lowercase).collect();
+                        let lower = LitStr::new(&lower, lit.span());
+                        quote::quote_spanned! {lit.span()=>
+                            let (#next_var, #path_var) = #path_var.split_once('/').unwrap_or((#path_var, ""));
+                            if !#next_var.chars().flat_map(char::to_lowercase).eq(#lower.chars()) {
+                                return Err(#err_404);
+                            }
+                        }
+                    });
+                    quote::quote!(#(#lits)*)
+                }
             }
-        },
-        Segment::Param(name) => quote::quote_spanned! {name.span()=>
+        }
+        Group::Param(name) => quote::quote_spanned! {name.span()=>
             let (#next_var, #path_var) = #path_var.split_once('/').unwrap_or((#path_var, ""));
-            let #name = #next_var.parse().map_err(|_|#err_404)?;
+            let #name = ::tackt::FromSegment::from_segment(#req_var, #next_var)?;
         },
-        Segment::Wild(name) => quote::quote_spanned! {name.span()=>
-            let #name = #path_var.to_string();
+        Group::Wild(name) => quote::quote_spanned! {name.span()=>
+            let #name = ::tackt::FromSegment::from_segment(#req_var, #path_var)?;
             let #path_var = "";
         },
     });
@@ -85,9 +317,24 @@ pub(crate) fn expand_impl(item: &ItemStruct, spec: &Spec) -> Result<TokenStream>
     let method_matching = match spec.methods.len() {
         0 => None,
         _ => Some({
-            let methods = spec.methods.iter().map(|method| {
-                quote::quote_spanned! {method.span()=>
+            let methods = spec.methods.iter().map(|method| match method {
+                Method::Standard(method) => quote::quote_spanned! {method.span()=>
                     ::tackt::Method::#method != #method_var
+                },
+                // The method is parsed once, it is a valid token since the
+                // spec is validated.
+                Method::Extension(method) => {
+                    let bytes = LitByteStr::new(method.value().as_bytes(), method.span());
+                    quote::quote_spanned! {method.span()=>
+                        {
+                            static METHOD: ::std::sync::OnceLock<::tackt::Method> =
+                                ::std::sync::OnceLock::new();
+                            METHOD.get_or_init(|| {
+                                ::tackt::Method::from_bytes(#bytes)
+                                    .expect("BUG: a validation is missed.")
+                            })
+                        } != #method_var
+                    }
                 }
             });
 
@@ -99,38 +346,42 @@ pub(crate) fn expand_impl(item: &ItemStruct, spec: &Spec) -> Result<TokenStream>
         }),
     };
 
-    let struct_name = &item.ident;
+    quote::quote! {
+        let (#next_var, #path_var) = #path_var.split_once('/').ok_or(#err_404)?;
+        if !#next_var.is_empty() {
+            return ::std::result::Result::Err(#err_404);
+        };
 
-    Ok(quote::quote! {
-        #[automatically_derived]
-        #[allow(unused_qualifications)]
-        impl<T> ::tackt::Param<T> for #struct_name
-        where T: ::tackt::PathReq + ::tackt::MethodReq,
-        {
-            fn from_request(#req_var: &T) -> ::std::result::Result<Self, #err_typ> {
-                let #path_var = ::tackt::PathReq::path(#req_var);
-                let #method_var = ::tackt::MethodReq::method(#req_var);
-                let (#next_var, #path_var) = #path_var.split_once('/').ok_or(#err_404)?;
-                if !#next_var.is_empty() {
-                    return ::std::result::Result::Err(#err_404);
-                };
+        #(#segment_matching)*
 
-                #(#segment_matching)*
+        if !#path_var.is_empty() {
+            return ::std::result::Result::Err(#err_404);
+        };
 
-                if !#path_var.is_empty() {
-                    return ::std::result::Result::Err(#err_404);
-                };
+        #method_matching
 
-                #method_matching
+        ::std::result::Result::Ok(#ctor)
+    }
+}
 
-                ::std::result::Result::Ok(
-                    #struct_name {
-                        #(#fields),*
-                    }
-                )
-            }
+enum Group<'a> {
+    Lit(Vec<&'a LitStr>),
+    Param(&'a Ident),
+    Wild(&'a Ident),
+}
+
+/// Group consecutive literal segments together.
+fn group_literals(segments: &[Segment]) -> impl Iterator<Item = Group<'_>> {
+    let mut groups = Vec::new();
+    for segment in segments {
+        match (segment, groups.last_mut()) {
+            (Segment::Lit(lit), Some(Group::Lit(lits))) => lits.push(lit),
+            (Segment::Lit(lit), _) => groups.push(Group::Lit(vec![lit])),
+            (Segment::Param(name), _) => groups.push(Group::Param(name)),
+            (Segment::Wild(name), _) => groups.push(Group::Wild(name)),
         }
-    })
+    }
+    groups.into_iter()
 }
 
 pub(crate) fn expand_struct(item: &ItemFn, skip: usize) -> Result<ItemStruct> {
@@ -0,0 +1,64 @@
+use syn::Ident;
+
+/// Find the candidate that is most likely a typo of `name`.
+pub(crate) fn suggest<'a, I>(name: &Ident, candidates: I) -> Option<&'a Ident>
+where
+    I: IntoIterator<Item = &'a Ident>,
+{
+    let name = name.to_string();
+    let max = std::cmp::max(1, name.len() / 3);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (distance(&name, &candidate.to_string()), candidate))
+        .filter(|(distance, _)| *distance <= max)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Append a "did you mean" to `message` if there is a suggestion.
+pub(crate) fn did_you_mean<'a, I>(message: String, name: &Ident, candidates: I) -> String
+where
+    I: IntoIterator<Item = &'a Ident>,
+{
+    match suggest(name, candidates) {
+        Some(suggestion) => format!("{message}, did you mean `{suggestion}`?"),
+        None => message,
+    }
+}
+
+/// Levenshtein distance.
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let cost = match a == *b {
+                true => prev,
+                false => prev + 1,
+            };
+            prev = row[j + 1];
+            row[j + 1] = std::cmp::min(cost, std::cmp::min(row[j], row[j + 1]) + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+#[test]
+#[cfg(test)]
+fn test() {
+    assert_eq!(distance("id", "id"), 0);
+    assert_eq!(distance("id", "ids"), 1);
+    assert_eq!(distance("name", "nmae"), 2);
+    assert_eq!(distance("", "abc"), 3);
+
+    let candidates: Vec<Ident> = vec![syn::parse_quote!(id), syn::parse_quote!(name)];
+    let name: Ident = syn::parse_quote!(ids);
+    assert_eq!(suggest(&name, &candidates).unwrap(), "id");
+    let name: Ident = syn::parse_quote!(path);
+    assert!(suggest(&name, &candidates).is_none());
+}
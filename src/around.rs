@@ -0,0 +1,168 @@
+use std::future::Future;
+use std::task::Context;
+use std::task::Poll;
+
+use tower_service::Service;
+
+use crate::error::Error;
+use crate::future::Maybe;
+use crate::future::Oneshot;
+use crate::route::Route;
+
+/// A middleware that runs around a route.
+///
+/// Note that application code cannot construct this struct directly. This is
+/// exported for type annotation only.
+#[derive(Clone, Copy, Debug)]
+pub struct Around<R, F> {
+    inner: R,
+    func: F,
+}
+
+impl<R, F> Around<R, F> {
+    #[inline]
+    pub(crate) fn new<T, U, E, Fut>(inner: R, func: F) -> Around<R, F>
+    where
+        R: Clone + Service<T>,
+        F: FnMut(T, Next<R>) -> Fut,
+        Fut: Future<Output = Result<U, E>>,
+    {
+        Around { inner, func }
+    }
+}
+
+impl<R, F, T, U, E, Fut> Service<T> for Around<R, F>
+where
+    R: Clone + Route<T>,
+    F: FnMut(T, Next<R>) -> Fut,
+    Fut: Future<Output = Result<U, E>>,
+    E: From<Error>,
+{
+    type Response = U;
+
+    type Error = E;
+
+    type Future = Maybe<Fut, Result<U, E>>;
+
+    #[inline]
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn call(&mut self, req: T) -> Self::Future {
+        match self.param(&req) {
+            Ok(param) => self.call_with_param(req, param),
+            Err(err) => Maybe::ready(Err(err.into())),
+        }
+    }
+}
+
+impl<R, F, T, U, E, Fut> Route<T> for Around<R, F>
+where
+    R: Clone + Route<T>,
+    F: FnMut(T, Next<R>) -> Fut,
+    Fut: Future<Output = Result<U, E>>,
+    E: From<Error>,
+{
+    type Param = R::Param;
+
+    #[inline]
+    fn call_with_param(&mut self, req: T, _: Self::Param) -> Self::Future {
+        Maybe::Future((self.func)(
+            req,
+            Next {
+                inner: self.inner.clone(),
+            },
+        ))
+    }
+
+    #[inline]
+    fn param(&self, req: &T) -> Result<Self::Param, Error> {
+        self.inner.param(req)
+    }
+}
+
+/// The rest of the router, as seen by an [`around`][1] middleware.
+///
+/// [1]: crate::router::Router::around
+#[derive(Clone, Copy, Debug)]
+pub struct Next<S> {
+    inner: S,
+}
+
+impl<S> Next<S> {
+    /// Pass the request to the rest of the router.
+    ///
+    /// The request is matched again, so a middleware may rewrite it.
+    #[inline]
+    pub fn run<T>(self, req: T) -> Oneshot<S, T>
+    where
+        S: Service<T>,
+    {
+        Oneshot::new(self.inner, req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Request;
+    use tower_service::Service;
+
+    use super::Next;
+    use crate::error::Error;
+    use crate::exec::run;
+    use crate::macros::param;
+    use crate::router::Router;
+
+    param!(Home, GET, "/");
+    param!(About, GET, "/about");
+
+    async fn home(_: Request<()>, _: Home) -> Result<String, Error> {
+        Ok("home".to_string())
+    }
+
+    async fn about(_: Request<()>, _: About) -> Result<String, Error> {
+        Ok("about".to_string())
+    }
+
+    async fn tag<S>(req: Request<()>, next: Next<S>) -> Result<String, Error>
+    where
+        S: Service<Request<()>, Response = String, Error = Error>,
+    {
+        if req.headers().contains_key("deny") {
+            return Ok("denied".to_string());
+        }
+        let res = next.run(req).await?;
+        Ok(format!("<{res}>"))
+    }
+
+    fn req(path: &'static str) -> Request<()> {
+        Request::builder()
+            .method(http::Method::GET)
+            .uri(http::Uri::from_static(path))
+            .body(())
+            .unwrap()
+    }
+
+    #[test]
+    fn test() {
+        let router = Router::new(home).around(tag).route(about);
+
+        let res = run(router, req("/"));
+        assert_eq!(res, Ok("<home>".to_string()));
+
+        let res = run(router, req("/about"));
+        assert_eq!(res, Ok("about".to_string()));
+
+        let mut denied = req("/");
+        denied.headers_mut().insert("deny", "1".try_into().unwrap());
+        let res = run(router, denied);
+        assert_eq!(res, Ok("denied".to_string()));
+
+        let mut denied = req("/nowhere");
+        denied.headers_mut().insert("deny", "1".try_into().unwrap());
+        let res = run(router, denied);
+        assert_eq!(res, Err(Error::Path));
+    }
+}
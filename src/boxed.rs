@@ -0,0 +1,341 @@
+use std::any::Any;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use tower_service::Service;
+
+use crate::error::Error;
+use crate::future::Maybe;
+use crate::list::List;
+use crate::priority::Priority;
+use crate::route::Route;
+use crate::router::Router;
+
+macro_rules! boxed {
+    (
+        $(#[$attr:meta])*
+        $name:ident, $router:ident, $erased:ident, $param:ident, $future:ident,
+        [$($sync:ident)*], [$($send:ident)*]
+    ) => {
+        trait $erased<T, U, E>: $($sync +)* {
+            fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), E>>;
+
+            fn call_with_param(
+                &mut self,
+                req: T,
+                param: Box<dyn Any $(+ $send)*>,
+            ) -> Pin<Box<dyn Future<Output = Result<U, E>> $(+ $send)*>>;
+
+            fn param(&self, req: &T) -> Result<$param, Error>;
+
+            fn clone_box(&self) -> Box<dyn $erased<T, U, E>>;
+        }
+
+        impl<R, T, U, E> $erased<T, U, E> for R
+        where
+            R: Clone + Route<T, Response = U, Error = E> $(+ $sync)* + 'static,
+            R::Param: Any $(+ $send)*,
+            R::Future: 'static $(+ $send)*,
+        {
+            #[inline]
+            fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), E>> {
+                Service::poll_ready(self, cx)
+            }
+
+            fn call_with_param(
+                &mut self,
+                req: T,
+                param: Box<dyn Any $(+ $send)*>,
+            ) -> Pin<Box<dyn Future<Output = Result<U, E>> $(+ $send)*>> {
+                match param.downcast::<R::Param>() {
+                    Ok(param) => Box::pin(Route::call_with_param(self, req, *param)),
+                    Err(_) => panic!("BUG: boxed route should call param from Route trait."),
+                }
+            }
+
+            #[inline]
+            fn param(&self, req: &T) -> Result<$param, Error> {
+                match Route::param(self, req) {
+                    Ok(param) => {
+                        let priority = crate::param::Param::<T>::priority(&param);
+                        Ok($param(Box::new(param), priority))
+                    }
+                    Err(err) => Err(err),
+                }
+            }
+
+            #[inline]
+            fn clone_box(&self) -> Box<dyn $erased<T, U, E>> {
+                Box::new(self.clone())
+            }
+        }
+
+        pub type $future<U, E> = Maybe<
+            Pin<Box<dyn Future<Output = Result<U, E>> $(+ $send)*>>,
+            Result<U, E>,
+        >;
+
+        pub struct $param(Box<dyn Any $(+ $send)*>, Priority);
+
+        impl std::fmt::Debug for $param {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple(stringify!($param)).finish()
+            }
+        }
+
+        impl<T> crate::param::Param<T> for $param {
+            #[inline]
+            fn from_request(_: &T) -> Result<Self, Error> {
+                panic!("BUG: boxed route should call param from Route trait.");
+            }
+
+            #[inline]
+            fn priority(&self) -> Priority {
+                self.1
+            }
+        }
+
+        $(#[$attr])*
+        pub struct $name<T, U, E> {
+            inner: Box<dyn $erased<T, U, E>>,
+        }
+
+        impl<T, U, E> $name<T, U, E> {
+            #[inline]
+            pub(crate) fn new<R>(route: R) -> $name<T, U, E>
+            where
+                R: Clone + Route<T, Response = U, Error = E> $(+ $sync)* + 'static,
+                R::Param: Any $(+ $send)*,
+                R::Future: 'static $(+ $send)*,
+            {
+                $name {
+                    inner: Box::new(route),
+                }
+            }
+        }
+
+        impl<T, U, E> Clone for $name<T, U, E> {
+            #[inline]
+            fn clone(&self) -> Self {
+                $name {
+                    inner: self.inner.clone_box(),
+                }
+            }
+        }
+
+        impl<T, U, E> std::fmt::Debug for $name<T, U, E> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($name)).finish_non_exhaustive()
+            }
+        }
+
+        impl<T, U, E> Service<T> for $name<T, U, E>
+        where
+            E: From<Error>,
+        {
+            type Response = U;
+
+            type Error = E;
+
+            type Future = $future<U, E>;
+
+            #[inline]
+            fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                self.inner.poll_ready(cx)
+            }
+
+            fn call(&mut self, req: T) -> Self::Future {
+                match Route::param(self, &req) {
+                    Err(err) => Maybe::ready(Err(err.into())),
+                    Ok(param) => Route::call_with_param(self, req, param),
+                }
+            }
+        }
+
+        impl<T, U, E> Route<T> for $name<T, U, E>
+        where
+            E: From<Error>,
+        {
+            type Param = $param;
+
+            #[inline]
+            fn call_with_param(&mut self, req: T, param: Self::Param) -> Self::Future {
+                Maybe::Future(self.inner.call_with_param(req, param.0))
+            }
+
+            #[inline]
+            fn param(&self, req: &T) -> Result<Self::Param, Error> {
+                self.inner.param(req)
+            }
+        }
+
+        /// Collect routers into one router.
+        ///
+        /// The routers are tried in iteration order. A router that does not
+        /// have any route does not match any request.
+        impl<T, U, E> FromIterator<$router<T, U, E>> for $router<T, U, E>
+        where
+            T: 'static,
+            U: 'static $(+ $send)*,
+            E: From<Error> + 'static $(+ $send)*,
+        {
+            fn from_iter<I>(iter: I) -> Self
+            where
+                I: IntoIterator<Item = $router<T, U, E>>,
+            {
+                let routes = iter.into_iter().map(Router::into_inner).collect();
+                Router::from_inner($name::new(List::new(routes)))
+            }
+        }
+    };
+}
+
+boxed! {
+    /// A route with its type erased.
+    ///
+    /// Every [`Router::route`] call nests the router type further. Boxing
+    /// erases that type, so a router can be stored in a struct field or built
+    /// from a list of routers at runtime. This route is `Send` and `Sync`, see
+    /// [`UnsyncBoxRoute`] for routes that are not.
+    ///
+    /// Note that application code cannot construct this struct directly. This
+    /// is exported for type annotation only. See [`Router::boxed`].
+    BoxRoute, BoxRouter, Erased, Param, BoxFuture, [Send Sync], [Send]
+}
+
+boxed! {
+    /// A route with its type erased, that is neither `Send` nor `Sync`.
+    ///
+    /// See [`BoxRoute`].
+    ///
+    /// Note that application code cannot construct this struct directly. This
+    /// is exported for type annotation only. See [`Router::boxed_unsync`].
+    UnsyncBoxRoute, UnsyncBoxRouter, UnsyncErased, UnsyncParam, UnsyncBoxFuture, [], []
+}
+
+/// A router with its type erased.
+///
+/// See [`BoxRoute`].
+pub type BoxRouter<T, U, E> = Router<BoxRoute<T, U, E>>;
+
+/// A router with its type erased, that is neither `Send` nor `Sync`.
+///
+/// See [`UnsyncBoxRoute`].
+pub type UnsyncBoxRouter<T, U, E> = Router<UnsyncBoxRoute<T, U, E>>;
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use http::Request;
+
+    use super::BoxRouter;
+    use super::UnsyncBoxRouter;
+
+    use crate::error::Error;
+    use crate::exec::run;
+    use crate::macros::param;
+    use crate::router::Router;
+
+    param!(Home, GET, "/");
+    param!(Users, GET, "/users");
+    param!(Posts, GET, "/posts");
+
+    async fn home(_: Request<()>, _: Home) -> Result<&'static str, Error> {
+        Ok("home")
+    }
+
+    async fn users(_: Request<()>, _: Users) -> Result<&'static str, Error> {
+        Ok("users")
+    }
+
+    async fn posts(_: Request<()>, _: Posts) -> Result<&'static str, Error> {
+        Ok("posts")
+    }
+
+    fn req(method: http::Method, path: &'static str) -> Request<()> {
+        Request::builder()
+            .method(method)
+            .uri(http::Uri::from_static(path))
+            .body(())
+            .unwrap()
+    }
+
+    struct App {
+        router: BoxRouter<Request<()>, &'static str, Error>,
+    }
+
+    #[test]
+    fn test() {
+        let app = App {
+            router: Router::new(home).route(users).boxed(),
+        };
+        let router = app.router.route(posts);
+
+        let res = run(router.clone(), req(http::Method::GET, "/"));
+        assert_eq!(res, Ok("home"));
+
+        let res = run(router.clone(), req(http::Method::GET, "/users"));
+        assert_eq!(res, Ok("users"));
+
+        let res = run(router.clone(), req(http::Method::GET, "/posts"));
+        assert_eq!(res, Ok("posts"));
+
+        let res = run(router, req(http::Method::GET, "/nowhere"));
+        assert_eq!(res, Err(Error::Path));
+    }
+
+    #[test]
+    fn from_iter() {
+        let plugins: Vec<BoxRouter<Request<()>, &'static str, Error>> = vec![
+            Router::new(home).boxed(),
+            Router::new(users).boxed(),
+            Router::new(posts).boxed(),
+        ];
+        let router: BoxRouter<_, _, _> = plugins.into_iter().collect();
+
+        let res = run(router.clone(), req(http::Method::GET, "/"));
+        assert_eq!(res, Ok("home"));
+
+        let res = run(router.clone(), req(http::Method::GET, "/posts"));
+        assert_eq!(res, Ok("posts"));
+
+        let res = run(router.clone(), req(http::Method::POST, "/users"));
+        assert_eq!(res, Err(Error::Method));
+
+        let res = run(router, req(http::Method::GET, "/nowhere"));
+        assert_eq!(res, Err(Error::Path));
+
+        let router: BoxRouter<Request<()>, &'static str, Error> = std::iter::empty().collect();
+        let res = run(router, req(http::Method::GET, "/"));
+        assert_eq!(res, Err(Error::Path));
+    }
+
+    #[test]
+    fn unsync() {
+        let name = Rc::new("shared");
+        let router: UnsyncBoxRouter<Request<()>, &'static str, Error> =
+            Router::new(move |_: Request<()>, _: Home| {
+                let name = name.clone();
+                async move { Ok(*name) }
+            })
+            .boxed_unsync();
+        let router: UnsyncBoxRouter<_, _, _> = [router, Router::new(users).boxed_unsync()]
+            .into_iter()
+            .collect();
+
+        let res = run(router.clone(), req(http::Method::GET, "/"));
+        assert_eq!(res, Ok("shared"));
+
+        let res = run(router, req(http::Method::GET, "/users"));
+        assert_eq!(res, Ok("users"));
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert<T: Send + Sync>() {}
+        assert::<BoxRouter<Request<()>, &'static str, Error>>();
+    }
+}
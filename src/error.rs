@@ -1,3 +1,5 @@
+use http::StatusCode;
+
 /// Error returned when a route does not match.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Error {
@@ -9,6 +11,41 @@ pub enum Error {
     Prefix,
 }
 
+impl Error {
+    /// The HTTP status code that describes this error.
+    ///
+    /// [`Error::Path`] and [`Error::Prefix`] are `404 Not Found`, while
+    /// [`Error::Method`] is `405 Method Not Allowed`.
+    pub const fn status_code(&self) -> StatusCode {
+        match self {
+            Error::Path | Error::Prefix => StatusCode::NOT_FOUND,
+            Error::Method => StatusCode::METHOD_NOT_ALLOWED,
+        }
+    }
+
+    /// Convert this error into a response with the error message as the body.
+    ///
+    /// [`From`] and [`IntoResponse`][1] give the same response.
+    ///
+    /// [1]: crate::response::IntoResponse
+    #[cfg(feature = "response")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+    pub fn into_response<B: From<String>>(self) -> http::Response<B> {
+        let mut res = http::Response::new(B::from(self.to_string()));
+        *res.status_mut() = self.status_code();
+        res
+    }
+}
+
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+impl<B: From<String>> From<Error> for http::Response<B> {
+    #[inline]
+    fn from(err: Error) -> Self {
+        err.into_response()
+    }
+}
+
 impl std::error::Error for Error {}
 
 impl std::fmt::Display for Error {
@@ -20,3 +57,29 @@ impl std::fmt::Display for Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::Error;
+
+    #[test]
+    fn status_code() {
+        assert_eq!(Error::Path.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(Error::Prefix.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(Error::Method.status_code(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[test]
+    #[cfg(feature = "response")]
+    fn into_response() {
+        let res: http::Response<String> = Error::Method.into();
+        assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(res.body(), "route method does not match");
+
+        let res: http::Response<String> = Error::Path.into_response();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        assert_eq!(res.body(), "route path does not match");
+    }
+}
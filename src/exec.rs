@@ -20,22 +20,88 @@ impl Wake for Waker {
     }
 }
 
-/// Execute an async.
+/// Execute an async, parking the thread while it is pending.
 pub(crate) fn oneshot<T>(fut: impl Future<Output = T>) -> T {
     let mut fut = Box::pin(fut);
     let waker = Waker::new().into();
     let mut cx = Context::from_waker(&waker);
 
-    match fut.as_mut().poll(&mut cx) {
-        Poll::Ready(out) => out,
-        Poll::Pending => panic!("still pending!!!"),
+    loop {
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(out) => return out,
+            Poll::Pending => std::thread::park(),
+        }
     }
 }
 
-/// Run a service.
+/// Yield once, returning `Pending` the first time it's polled.
+pub(crate) async fn yield_now() {
+    let mut yielded = false;
+    std::future::poll_fn(|cx| {
+        if yielded {
+            return Poll::Ready(());
+        }
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
+}
+
+/// Run a service, waiting for it to be ready first.
 pub(crate) fn run<S, T>(mut service: S, req: T) -> Result<S::Response, S::Error>
 where
     S: Service<T>,
 {
-    oneshot(service.call(req))
+    oneshot(async move {
+        std::future::poll_fn(|cx| service.poll_ready(cx)).await?;
+        service.call(req).await
+    })
+}
+
+/// A service that is not ready on its first `poll_ready`, and panics if it is
+/// called before being ready.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Gate<S> {
+    inner: S,
+    polled: bool,
+    ready: bool,
+}
+
+impl<S> Gate<S> {
+    pub(crate) fn new(inner: S) -> Gate<S> {
+        Gate {
+            inner,
+            polled: false,
+            ready: false,
+        }
+    }
+}
+
+impl<S, T> Service<T> for Gate<S>
+where
+    S: Service<T>,
+{
+    type Response = S::Response;
+
+    type Error = S::Error;
+
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if !self.polled {
+            self.polled = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        let ready = self.inner.poll_ready(cx);
+        self.ready = matches!(ready, Poll::Ready(Ok(())));
+        ready
+    }
+
+    fn call(&mut self, req: T) -> Self::Future {
+        assert!(self.ready, "called before ready");
+        self.ready = false;
+        self.inner.call(req)
+    }
 }
@@ -0,0 +1,515 @@
+use std::convert::Infallible;
+use std::future;
+use std::future::Ready;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Buf;
+use bytes::Bytes;
+use http::HeaderMap;
+use http::Method;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use http::Uri;
+use http_body::Body;
+
+use crate::request::ExtensionReq;
+use crate::response::IntoResponse;
+
+/// A handler argument that is extracted from a borrowed request.
+///
+/// Unlike [`Param`][1], which decides whether a route matches, an extractor
+/// runs after the route has matched. A failing extractor produces a response
+/// through its [`Rejection`][FromRequestParts::Rejection] instead of letting
+/// other routes try.
+///
+/// Every argument of a handler except the last two are extracted with this
+/// trait. The last two arguments are the body-consuming [`FromRequest`]
+/// argument and the route [`Param`][1].
+///
+/// [1]: crate::param::Param
+pub trait FromRequestParts<T>: Sized {
+    /// The response returned when extraction fails.
+    type Rejection;
+
+    /// Extract the value from request.
+    fn from_request_parts(req: &T) -> Result<Self, Self::Rejection>;
+}
+
+/// A handler argument that consumes the request.
+///
+/// This is the second to last argument of a handler, right before the route
+/// [`Param`][1]. The request itself is the simplest implementor. Any
+/// [`FromRequestParts`] implementor can also be used here.
+///
+/// `M` is only there to tell those two kinds of implementors apart. It
+/// should be left as default when implementing this trait.
+///
+/// [1]: crate::param::Param
+pub trait FromRequest<T, M = ViaRequest>: Sized {
+    /// The response returned when extraction fails.
+    type Rejection;
+
+    /// The future returned by [`FromRequest::from_request`].
+    type Future: future::Future<Output = Result<Self, Self::Rejection>>;
+
+    /// Extract the value from request.
+    fn from_request(req: T) -> Self::Future;
+}
+
+/// Marker for [`FromRequest`] implemented directly.
+#[derive(Clone, Copy, Debug)]
+pub struct ViaRequest;
+
+/// Marker for [`FromRequest`] implemented through [`FromRequestParts`].
+#[derive(Clone, Copy, Debug)]
+pub struct ViaParts;
+
+impl<T> FromRequest<T> for T {
+    type Rejection = Infallible;
+
+    type Future = Ready<Result<T, Infallible>>;
+
+    #[inline]
+    fn from_request(req: T) -> Self::Future {
+        future::ready(Ok(req))
+    }
+}
+
+impl<T, X> FromRequest<T, ViaParts> for X
+where
+    X: FromRequestParts<T>,
+{
+    type Rejection = X::Rejection;
+
+    type Future = Ready<Result<X, X::Rejection>>;
+
+    #[inline]
+    fn from_request(req: T) -> Self::Future {
+        future::ready(X::from_request_parts(&req))
+    }
+}
+
+impl<T, X> FromRequestParts<T> for Option<X>
+where
+    X: FromRequestParts<T>,
+{
+    type Rejection = Infallible;
+
+    #[inline]
+    fn from_request_parts(req: &T) -> Result<Self, Self::Rejection> {
+        Ok(X::from_request_parts(req).ok())
+    }
+}
+
+impl<B> FromRequestParts<Request<B>> for HeaderMap {
+    type Rejection = Infallible;
+
+    #[inline]
+    fn from_request_parts(req: &Request<B>) -> Result<Self, Self::Rejection> {
+        Ok(req.headers().clone())
+    }
+}
+
+impl<B> FromRequestParts<Request<B>> for Method {
+    type Rejection = Infallible;
+
+    #[inline]
+    fn from_request_parts(req: &Request<B>) -> Result<Self, Self::Rejection> {
+        Ok(req.method().clone())
+    }
+}
+
+impl<B> FromRequestParts<Request<B>> for Uri {
+    type Rejection = Infallible;
+
+    #[inline]
+    fn from_request_parts(req: &Request<B>) -> Result<Self, Self::Rejection> {
+        Ok(req.uri().clone())
+    }
+}
+
+/// Extract a value from request extensions.
+///
+/// The value is cloned, so it should be cheap to clone.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Extension<X>(pub X);
+
+impl<T, X> FromRequestParts<T> for Extension<X>
+where
+    T: ExtensionReq,
+    X: Clone + Send + Sync + 'static,
+{
+    type Rejection = Rejection;
+
+    #[inline]
+    fn from_request_parts(req: &T) -> Result<Self, Self::Rejection> {
+        req.extensions()
+            .get::<X>()
+            .cloned()
+            .map(Extension)
+            .ok_or(Rejection::MissingExtension(std::any::type_name::<X>()))
+    }
+}
+
+/// The largest request body that [`Bytes`], [`String`] and `Json` collect,
+/// in bytes.
+///
+/// A larger body is rejected with [`Rejection::PayloadTooLarge`]. Without
+/// this value in the request extensions, [`BodyLimit::DEFAULT`] applies. Give
+/// it to [`Router::with_state`][1] to change the limit:
+///
+/// ```ignore
+/// let router = Router::handler(upload).with_state(BodyLimit(16 * 1024 * 1024));
+/// ```
+///
+/// [1]: crate::router::Router::with_state
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BodyLimit(pub usize);
+
+impl BodyLimit {
+    /// The limit when none is given, 2 MiB.
+    pub const DEFAULT: BodyLimit = BodyLimit(2 * 1024 * 1024);
+}
+
+impl Default for BodyLimit {
+    #[inline]
+    fn default() -> Self {
+        BodyLimit::DEFAULT
+    }
+}
+
+impl<B> FromRequest<Request<B>> for Bytes
+where
+    B: Body,
+    B::Error: std::fmt::Display,
+{
+    type Rejection = Rejection;
+
+    type Future = Collect<B, Bytes>;
+
+    #[inline]
+    fn from_request(req: Request<B>) -> Self::Future {
+        Collect::new(req, |body| Ok(Bytes::from(body)))
+    }
+}
+
+impl<B> FromRequest<Request<B>> for String
+where
+    B: Body,
+    B::Error: std::fmt::Display,
+{
+    type Rejection = Rejection;
+
+    type Future = Collect<B, String>;
+
+    #[inline]
+    fn from_request(req: Request<B>) -> Self::Future {
+        Collect::new(req, |body| {
+            String::from_utf8(body).map_err(|_| Rejection::InvalidUtf8)
+        })
+    }
+}
+
+/// The reason an extractor fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Rejection {
+    /// Request extensions do not contain a value of this type.
+    MissingExtension(&'static str),
+    /// Router does not have a state of this type.
+    MissingState(&'static str),
+    /// Request body cannot be read.
+    InvalidBody(String),
+    /// Request body is larger than the [`BodyLimit`].
+    PayloadTooLarge,
+    /// Request body is not a valid UTF-8.
+    InvalidUtf8,
+    /// Request query cannot be deserialized.
+    InvalidQuery(String),
+    /// Request body is not a JSON.
+    UnsupportedMediaType,
+    /// Request body cannot be deserialized.
+    InvalidJson(String),
+}
+
+impl Rejection {
+    /// The HTTP status code that describes this rejection.
+    pub const fn status_code(&self) -> StatusCode {
+        match self {
+            Rejection::MissingExtension(..) => StatusCode::INTERNAL_SERVER_ERROR,
+            Rejection::MissingState(..) => StatusCode::INTERNAL_SERVER_ERROR,
+            Rejection::InvalidBody(..) => StatusCode::BAD_REQUEST,
+            Rejection::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Rejection::InvalidUtf8 => StatusCode::BAD_REQUEST,
+            Rejection::InvalidQuery(..) => StatusCode::BAD_REQUEST,
+            Rejection::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Rejection::InvalidJson(..) => StatusCode::UNPROCESSABLE_ENTITY,
+        }
+    }
+}
+
+impl std::error::Error for Rejection {}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Rejection::MissingExtension(name) => write!(f, "missing request extension: {name}"),
+            Rejection::MissingState(name) => write!(f, "missing router state: {name}"),
+            Rejection::InvalidBody(err) => write!(f, "invalid request body: {err}"),
+            Rejection::PayloadTooLarge => f.write_str("request body is too large"),
+            Rejection::InvalidUtf8 => f.write_str("request body is not a valid UTF-8"),
+            Rejection::InvalidQuery(err) => write!(f, "invalid request query: {err}"),
+            Rejection::UnsupportedMediaType => f.write_str("expected a JSON request body"),
+            Rejection::InvalidJson(err) => write!(f, "invalid JSON request body: {err}"),
+        }
+    }
+}
+
+impl<B: From<String>> IntoResponse<B> for Rejection {
+    fn into_response(self) -> Response<B> {
+        let mut res = Response::new(B::from(self.to_string()));
+        *res.status_mut() = self.status_code();
+        res
+    }
+}
+
+/// Collect a request body, up to the [`BodyLimit`], then convert it.
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct Collect<B, X> {
+    #[pin]
+    body: B,
+    buf: Vec<u8>,
+    limit: usize,
+    convert: fn(Vec<u8>) -> Result<X, Rejection>,
+    rejection: Option<Rejection>,
+}
+
+impl<B, X> Collect<B, X> {
+    /// Collect the body of `req`, rejecting it without reading when its size
+    /// hint is already over the limit.
+    pub(crate) fn new(
+        req: Request<B>,
+        convert: fn(Vec<u8>) -> Result<X, Rejection>,
+    ) -> Collect<B, X>
+    where
+        B: Body,
+    {
+        let limit = req
+            .extensions()
+            .get::<BodyLimit>()
+            .copied()
+            .unwrap_or_default()
+            .0;
+        let body = req.into_body();
+        let rejection = match body.size_hint().upper() {
+            Some(upper) if upper > limit as u64 => Some(Rejection::PayloadTooLarge),
+            _ => None,
+        };
+        Collect {
+            body,
+            buf: Vec::new(),
+            limit,
+            convert,
+            rejection,
+        }
+    }
+
+    /// A future that rejects without reading the body.
+    #[inline]
+    #[cfg(feature = "json")]
+    pub(crate) fn reject(body: B, rejection: Rejection) -> Collect<B, X> {
+        Collect {
+            body,
+            buf: Vec::new(),
+            limit: 0,
+            convert: |_| unreachable!("rejected body is never converted"),
+            rejection: Some(rejection),
+        }
+    }
+}
+
+impl<B, X> future::Future for Collect<B, X>
+where
+    B: Body,
+    B::Error: std::fmt::Display,
+{
+    type Output = Result<X, Rejection>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        if let Some(rejection) = this.rejection.take() {
+            return Poll::Ready(Err(rejection));
+        }
+        loop {
+            match this.body.as_mut().poll_data(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(None) => {
+                    let buf = std::mem::take(this.buf);
+                    return Poll::Ready((this.convert)(buf));
+                }
+                Poll::Ready(Some(Err(err))) => {
+                    return Poll::Ready(Err(Rejection::InvalidBody(err.to_string())))
+                }
+                Poll::Ready(Some(Ok(mut data))) => {
+                    if data.remaining() > *this.limit - this.buf.len() {
+                        return Poll::Ready(Err(Rejection::PayloadTooLarge));
+                    }
+                    while data.has_remaining() {
+                        let chunk = data.chunk();
+                        let len = chunk.len();
+                        this.buf.extend_from_slice(chunk);
+                        data.advance(len);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use http::HeaderMap;
+    use http::Request;
+    use http::Response;
+    use http::StatusCode;
+
+    use super::BodyLimit;
+    use super::Extension;
+    use super::FromRequest;
+    use super::Rejection;
+    use crate::exec::oneshot;
+    use crate::exec::run;
+    use crate::macros::param;
+    use crate::router::Router;
+
+    #[test]
+    fn extract() {
+        let req = Request::new("body".to_string());
+        let res = oneshot(<Request<String> as FromRequest<_>>::from_request(req));
+        assert!(matches!(res, Ok(req) if req.body() == "body"));
+
+        let req = Request::new("body".to_string());
+        let res = oneshot(String::from_request(req));
+        assert_eq!(res, Ok("body".to_string()));
+
+        let req = Request::new("body".to_string());
+        let res = oneshot(Bytes::from_request(req));
+        assert_eq!(res, Ok(Bytes::from_static(b"body")));
+
+        let req = Request::new(());
+        let res = oneshot(Extension::<i32>::from_request(req));
+        assert_eq!(res, Err(Rejection::MissingExtension("i32")));
+
+        let mut req = Request::new(());
+        req.extensions_mut().insert(1i32);
+        let res = oneshot(Extension::<i32>::from_request(req));
+        assert_eq!(res, Ok(Extension(1)));
+    }
+
+    param!(Echo, POST, "/");
+
+    async fn echo(
+        headers: HeaderMap,
+        ext: Option<Extension<i32>>,
+        body: String,
+        _: Echo,
+    ) -> (StatusCode, String) {
+        let name = headers["name"].to_str().unwrap();
+        (StatusCode::OK, format!("{name} {ext:?} {body}"))
+    }
+
+    async fn missing(_: Extension<i32>, _: Request<String>, _: Echo) -> &'static str {
+        "unreachable"
+    }
+
+    fn req(body: &'static str) -> Request<String> {
+        Request::builder()
+            .method(http::Method::POST)
+            .uri(http::Uri::from_static("/"))
+            .header("name", "someone")
+            .body(body.to_string())
+            .unwrap()
+    }
+
+    /// A body sent in chunks, without a size hint.
+    struct Chunks(Vec<&'static str>);
+
+    impl http_body::Body for Chunks {
+        type Data = Bytes;
+
+        type Error = std::convert::Infallible;
+
+        fn poll_data(
+            mut self: std::pin::Pin<&mut Self>,
+            _: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Option<Result<Bytes, Self::Error>>> {
+            let chunk = match self.0.is_empty() {
+                true => None,
+                false => Some(Ok(Bytes::from_static(self.0.remove(0).as_bytes()))),
+            };
+            std::task::Poll::Ready(chunk)
+        }
+
+        fn poll_trailers(
+            self: std::pin::Pin<&mut Self>,
+            _: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Result<Option<HeaderMap>, Self::Error>> {
+            std::task::Poll::Ready(Ok(None))
+        }
+    }
+
+    #[test]
+    fn limit() {
+        let mut req = Request::new("body".to_string());
+        req.extensions_mut().insert(BodyLimit(4));
+        let res = oneshot(String::from_request(req));
+        assert_eq!(res, Ok("body".to_string()));
+
+        // rejected from the size hint, before reading.
+        let mut req = Request::new("body".to_string());
+        req.extensions_mut().insert(BodyLimit(3));
+        let res = oneshot(Bytes::from_request(req));
+        assert_eq!(res, Err(Rejection::PayloadTooLarge));
+        assert_eq!(
+            Rejection::PayloadTooLarge.status_code(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+
+        let mut req = Request::new(Chunks(vec!["bo", "dy"]));
+        req.extensions_mut().insert(BodyLimit(4));
+        let res = oneshot(String::from_request(req));
+        assert_eq!(res, Ok("body".to_string()));
+
+        let mut req = Request::new(Chunks(vec!["bo", "dy", "!"]));
+        req.extensions_mut().insert(BodyLimit(4));
+        let res = oneshot(String::from_request(req));
+        assert_eq!(res, Err(Rejection::PayloadTooLarge));
+
+        let req = Request::new("a".repeat(BodyLimit::DEFAULT.0 + 1));
+        let res = oneshot(Bytes::from_request(req));
+        assert_eq!(res, Err(Rejection::PayloadTooLarge));
+    }
+
+    #[test]
+    fn handler() {
+        let router = Router::handler(echo);
+
+        let res: Response<String> = run(router, req("hello")).unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.body(), "someone None hello");
+
+        let mut request = req("hello");
+        request.extensions_mut().insert(1i32);
+        let res: Response<String> = run(router, request).unwrap();
+        assert_eq!(res.body(), "someone Some(Extension(1)) hello");
+
+        let router = Router::handler(missing);
+        let res: Response<String> = run(router, req("hello")).unwrap();
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,914 @@
+use std::future::Ready;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::marker::PhantomData;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use http::header;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Method;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use tower_service::Service;
+
+use crate::error::Error;
+
+/// Serve files under a directory.
+///
+/// The file is resolved from the request's path, which is the path left by
+/// [`Router::mount`][1]:
+///
+/// ```ignore
+/// let router = Router::new(home).mount("/assets", ServeDir::new("dist/assets"));
+/// ```
+///
+/// Every segment is percent-decoded, and a segment that would leave the
+/// directory, like `..`, is rejected. A directory is served by its
+/// `index.html`.
+///
+/// Only `GET` and `HEAD` are allowed, other methods return
+/// [`Error::Method`][2]. A file that does not exist returns
+/// [`Error::Path`][3], so [`Router::respond`][4] turns it into `404`. Any
+/// other I/O error becomes a `500 Internal Server Error` response.
+///
+/// The response has:
+///
+/// - `Content-Type` guessed from the extension.
+/// - `ETag` and `Last-Modified`, answering `If-None-Match` and
+///   `If-Modified-Since` with `304 Not Modified`.
+/// - `Accept-Ranges: bytes`, answering a single `Range` with
+///   `206 Partial Content`. `If-Range` is honored and multiple ranges are
+///   served as the whole file.
+///
+/// With [`ServeDir::precompressed_br`] and [`ServeDir::precompressed_gzip`],
+/// a sibling `.br` or `.gz` file is served instead when the request accepts
+/// the encoding.
+///
+/// With [`ServeDir::spa`], the `index.html` of the directory is served for
+/// client side routes.
+///
+/// Files are read whole into the response body, which must implement
+/// `From<Vec<u8>>` and `Default`. A response body larger than
+/// [`ServeDir::max_size`] is not read and answers
+/// `413 Payload Too Large`, the client may still fetch the file in ranges.
+/// This suits assets, not large downloads.
+///
+/// # Blocking
+///
+/// Every file system call, from finding the file to reading it, is a blocking
+/// `std::fs` call made on the task that polls the router. A slow disk or a
+/// network file system stalls that executor thread, and every other task on
+/// it, until the call returns. This is a hard limitation of `ServeDir`: serve
+/// large or slow files with a dedicated file server instead.
+///
+/// [1]: crate::router::Router::mount
+/// [2]: crate::error::Error::Method
+/// [3]: crate::error::Error::Path
+/// [4]: crate::router::Router::respond
+pub struct ServeDir<B, E = Error> {
+    inner: Arc<Inner>,
+    _marker: PhantomData<fn() -> (B, E)>,
+}
+
+#[derive(Clone, Debug)]
+struct Inner {
+    root: PathBuf,
+    br: bool,
+    gzip: bool,
+    spa: bool,
+    max_size: u64,
+}
+
+/// The default of [`ServeDir::max_size`], 8 MiB.
+const MAX_SIZE: u64 = 8 * 1024 * 1024;
+
+impl<B, E> ServeDir<B, E> {
+    /// Serve files under `root`.
+    pub fn new<P>(root: P) -> ServeDir<B, E>
+    where
+        P: Into<PathBuf>,
+    {
+        ServeDir {
+            inner: Arc::new(Inner {
+                root: root.into(),
+                br: false,
+                gzip: false,
+                spa: false,
+                max_size: MAX_SIZE,
+            }),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Serve `<file>.br` when the request accepts `br`.
+    pub fn precompressed_br(mut self) -> ServeDir<B, E> {
+        Arc::make_mut(&mut self.inner).br = true;
+        self
+    }
+
+    /// Serve `<file>.gz` when the request accepts `gzip`.
+    ///
+    /// `.br` is preferred when both are enabled and accepted.
+    pub fn precompressed_gzip(mut self) -> ServeDir<B, E> {
+        Arc::make_mut(&mut self.inner).gzip = true;
+        self
+    }
+
+    /// Fall back to `index.html` for a single-page app.
+    ///
+    /// A `GET` request for a file that does not exist is served with the
+    /// `index.html` at the root of the directory, when the request accepts
+    /// `text/html` and the last segment does not have the extension of a known
+    /// asset type, like `.js` or `.png`. So `/users/7` or `/users/john.doe`
+    /// renders the app, while a missing `/app.js` or `/app.css` is still not
+    /// found.
+    ///
+    /// ```ignore
+    /// let router = Router::new(api).mount("/app", ServeDir::new("dist").spa());
+    /// ```
+    pub fn spa(mut self) -> ServeDir<B, E> {
+        Arc::make_mut(&mut self.inner).spa = true;
+        self
+    }
+
+    /// Set the largest body read into memory for a response, in bytes.
+    ///
+    /// Defaults to 8 MiB. A larger file, or range of a file, is not read and
+    /// answers `413 Payload Too Large` with a plain text message and
+    /// `Accept-Ranges: bytes`, so the client can fetch the file in smaller
+    /// ranges instead.
+    pub fn max_size(mut self, max_size: u64) -> ServeDir<B, E> {
+        Arc::make_mut(&mut self.inner).max_size = max_size;
+        self
+    }
+}
+
+impl<B, E> Clone for ServeDir<B, E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ServeDir {
+            inner: self.inner.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<B, E> std::fmt::Debug for ServeDir<B, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServeDir")
+            .field("root", &self.inner.root)
+            .field("br", &self.inner.br)
+            .field("gzip", &self.inner.gzip)
+            .field("spa", &self.inner.spa)
+            .field("max_size", &self.inner.max_size)
+            .finish()
+    }
+}
+
+impl<A, B, E> Service<Request<A>> for ServeDir<B, E>
+where
+    B: From<Vec<u8>> + Default,
+    E: From<Error>,
+{
+    type Response = Response<B>;
+
+    type Error = E;
+
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    #[inline]
+    fn poll_ready(
+        &mut self,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<A>) -> Self::Future {
+        std::future::ready(self.inner.serve(&req).map_err(E::from))
+    }
+}
+
+/// A file to serve, with its encoding.
+struct Found {
+    path: PathBuf,
+    meta: std::fs::Metadata,
+    encoding: Option<&'static str>,
+}
+
+impl Inner {
+    fn serve<A, B>(&self, req: &Request<A>) -> Result<Response<B>, Error>
+    where
+        B: From<Vec<u8>> + Default,
+    {
+        if req.method() != Method::GET && req.method() != Method::HEAD {
+            return Err(Error::Method);
+        }
+        let path = resolve(&self.root, req.uri().path()).ok_or(Error::Path)?;
+
+        let found = match self.find(path, req.headers()) {
+            Err(err) if is_not_found(&err) && self.spa && is_navigation(req) => {
+                self.find(self.root.join("index.html"), req.headers())
+            }
+            found => found,
+        };
+        let found = match found {
+            Ok(found) => found,
+            Err(err) if is_not_found(&err) => return Err(Error::Path),
+            Err(_) => return Ok(empty(StatusCode::INTERNAL_SERVER_ERROR)),
+        };
+
+        match self.respond(req, found) {
+            Ok(res) => Ok(res),
+            Err(err) if is_not_found(&err) => Err(Error::Path),
+            Err(_) => Ok(empty(StatusCode::INTERNAL_SERVER_ERROR)),
+        }
+    }
+
+    /// Find the file at `path`, or a precompressed sibling of it.
+    fn find(&self, mut path: PathBuf, headers: &HeaderMap) -> std::io::Result<Found> {
+        let mut meta = std::fs::metadata(&path)?;
+        if meta.is_dir() {
+            path.push("index.html");
+            meta = std::fs::metadata(&path)?;
+        }
+        if !meta.is_file() {
+            return Err(std::io::ErrorKind::NotFound.into());
+        }
+
+        let siblings = [("br", "br", self.br), ("gzip", "gz", self.gzip)];
+        for (encoding, extension, enabled) in siblings {
+            if !enabled || !accepts(headers, header::ACCEPT_ENCODING, encoding) {
+                continue;
+            }
+            let mut sibling = path.clone().into_os_string();
+            sibling.push(".");
+            sibling.push(extension);
+            if let Ok(meta) = std::fs::metadata(&sibling) {
+                if meta.is_file() {
+                    return Ok(Found {
+                        path: sibling.into(),
+                        meta,
+                        encoding: Some(encoding),
+                    });
+                }
+            }
+        }
+
+        Ok(Found {
+            path,
+            meta,
+            encoding: None,
+        })
+    }
+
+    fn respond<A, B>(&self, req: &Request<A>, found: Found) -> std::io::Result<Response<B>>
+    where
+        B: From<Vec<u8>> + Default,
+    {
+        let len = found.meta.len();
+        let modified = found.meta.modified().ok();
+        let etag = etag(len, modified);
+        let last_modified = modified.map(httpdate::fmt_http_date);
+
+        let mut headers = HeaderMap::new();
+        if let Ok(etag) = HeaderValue::from_str(&etag) {
+            headers.insert(header::ETAG, etag);
+        }
+        if let Some(Ok(date)) = last_modified.as_deref().map(HeaderValue::from_str) {
+            headers.insert(header::LAST_MODIFIED, date);
+        }
+        if self.br || self.gzip {
+            headers.insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+        }
+
+        if !modified_since(req.headers(), &etag, modified) {
+            let mut res = empty(StatusCode::NOT_MODIFIED);
+            *res.headers_mut() = headers;
+            return Ok(res);
+        }
+
+        let content_type = content_type(&found.path, found.encoding.is_some());
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+        headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+        if let Some(encoding) = found.encoding {
+            headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(encoding));
+        }
+
+        let range = match req.headers().get(header::RANGE) {
+            Some(range) if if_range(req.headers(), &etag, last_modified.as_deref()) => {
+                parse_range(range.to_str().unwrap_or(""), len)
+            }
+            _ => Range::Full,
+        };
+        let (status, start, end) = match range {
+            Range::Full => (StatusCode::OK, 0, len),
+            Range::Partial(start, end) => {
+                let content_range = format!("bytes {}-{}/{}", start, end - 1, len);
+                if let Ok(content_range) = HeaderValue::from_str(&content_range) {
+                    headers.insert(header::CONTENT_RANGE, content_range);
+                }
+                (StatusCode::PARTIAL_CONTENT, start, end)
+            }
+            Range::Unsatisfiable => {
+                let content_range = format!("bytes */{}", len);
+                if let Ok(content_range) = HeaderValue::from_str(&content_range) {
+                    headers.insert(header::CONTENT_RANGE, content_range);
+                }
+                let mut res = empty(StatusCode::RANGE_NOT_SATISFIABLE);
+                *res.headers_mut() = headers;
+                return Ok(res);
+            }
+        };
+        if end - start > self.max_size {
+            let message = format!(
+                "file is larger than {} bytes, request it in ranges",
+                self.max_size
+            );
+            let mut res = Response::new(B::from(message.into_bytes()));
+            *res.status_mut() = StatusCode::PAYLOAD_TOO_LARGE;
+            let headers = res.headers_mut();
+            headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("text/plain; charset=utf-8"),
+            );
+            headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+            return Ok(res);
+        }
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from(end - start));
+
+        let body = match req.method() == Method::HEAD {
+            true => B::default(),
+            false => {
+                let mut file = std::fs::File::open(&found.path)?;
+                file.seek(SeekFrom::Start(start))?;
+                let mut body = Vec::new();
+                file.take(end - start).read_to_end(&mut body)?;
+                B::from(body)
+            }
+        };
+
+        let mut res = Response::new(body);
+        *res.status_mut() = status;
+        *res.headers_mut() = headers;
+        Ok(res)
+    }
+}
+
+fn empty<B: Default>(status: StatusCode) -> Response<B> {
+    let mut res = Response::new(B::default());
+    *res.status_mut() = status;
+    res
+}
+
+fn is_not_found(err: &std::io::Error) -> bool {
+    err.kind() == std::io::ErrorKind::NotFound
+}
+
+/// Resolve `path` under `root`.
+///
+/// Returns `None` when a segment is not valid percent-encoded UTF-8, or would
+/// not be a plain file name, like `..`.
+fn resolve(root: &Path, path: &str) -> Option<PathBuf> {
+    let path = match path {
+        "" => "",
+        path => path.strip_prefix('/')?,
+    };
+    let mut resolved = root.to_path_buf();
+    for segment in path.split('/') {
+        let segment = decode(segment)?;
+        if segment.is_empty() || segment == "." {
+            continue;
+        }
+        if segment.contains(['\\', '\0']) {
+            return None;
+        }
+        let mut components = Path::new(&segment).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(..)), None) => resolved.push(&segment),
+            _ => return None,
+        }
+    }
+    Some(resolved)
+}
+
+/// Percent-decode `segment`.
+fn decode(segment: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(segment.len());
+    let mut iter = segment.bytes();
+    while let Some(byte) = iter.next() {
+        match byte {
+            b'%' => {
+                let hi = (iter.next()? as char).to_digit(16)?;
+                let lo = (iter.next()? as char).to_digit(16)?;
+                bytes.push((hi * 16 + lo) as u8);
+            }
+            byte => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Wether a request for a missing file should get the app's `index.html`.
+fn is_navigation<A>(req: &Request<A>) -> bool {
+    let name = req.uri().path().rsplit('/').next().unwrap_or("");
+    // Only a known asset type is not found, so `/users/john.doe` still gets
+    // the app.
+    let html = matches!(
+        content_type(Path::new(name), false),
+        "text/html; charset=utf-8" | "application/octet-stream"
+    );
+    req.method() == Method::GET && html && accepts(req.headers(), header::ACCEPT, "text/html")
+}
+
+/// Wether the `name` header, like `Accept-Encoding`, accepts `value` with a
+/// non zero quality.
+fn accepts(headers: &HeaderMap, name: header::HeaderName, value: &str) -> bool {
+    let values = headers.get_all(name).iter();
+    let items = values
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','));
+    for item in items {
+        let mut params = item.split(';');
+        let item = params.next().unwrap_or("").trim();
+        if !item.eq_ignore_ascii_case(value) {
+            continue;
+        }
+        let quality = params
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|quality| quality.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        return quality > 0.0;
+    }
+    false
+}
+
+/// A strong validator from the size and the modification time.
+fn etag(len: u64, modified: Option<SystemTime>) -> String {
+    let modified = modified
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|modified| modified.as_nanos())
+        .unwrap_or(0);
+    format!("\"{:x}-{:x}\"", len, modified)
+}
+
+/// Wether the client's copy is outdated, according to `If-None-Match`, or
+/// `If-Modified-Since` when the former is missing.
+fn modified_since(headers: &HeaderMap, etag: &str, modified: Option<SystemTime>) -> bool {
+    if let Some(value) = headers.get(header::IF_NONE_MATCH) {
+        let value = value.to_str().unwrap_or("");
+        return !value
+            .split(',')
+            .map(str::trim)
+            .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag);
+    }
+
+    let since = headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| httpdate::parse_http_date(value).ok());
+    match (since, modified) {
+        // The date has a precision of one second.
+        (Some(since), Some(modified)) => match modified.duration_since(since) {
+            Ok(elapsed) => elapsed.as_secs() >= 1,
+            Err(..) => false,
+        },
+        _ => true,
+    }
+}
+
+/// Wether `Range` applies, according to `If-Range`.
+fn if_range(headers: &HeaderMap, etag: &str, last_modified: Option<&str>) -> bool {
+    match headers.get(header::IF_RANGE).map(HeaderValue::to_str) {
+        None => true,
+        Some(Ok(value)) => value == etag || Some(value) == last_modified,
+        Some(Err(..)) => false,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Range {
+    Full,
+    /// The start and the end, exclusive.
+    Partial(u64, u64),
+    Unsatisfiable,
+}
+
+/// Parse a single byte range. Anything else is served as the whole file.
+fn parse_range(value: &str, len: u64) -> Range {
+    let spec = match value.trim().strip_prefix("bytes=") {
+        Some(spec) if !spec.contains(',') => spec.trim(),
+        _ => return Range::Full,
+    };
+    let (start, end) = match spec.split_once('-') {
+        Some(bounds) => bounds,
+        None => return Range::Full,
+    };
+
+    let (start, end) = match (start.parse::<u64>(), end.parse::<u64>()) {
+        (Ok(start), Ok(end)) if start <= end => (start, end.saturating_add(1).min(len)),
+        (Ok(start), Err(..)) if end.is_empty() => (start, len),
+        (Err(..), Ok(suffix)) if start.is_empty() => match suffix {
+            0 => return Range::Unsatisfiable,
+            suffix => (len.saturating_sub(suffix), len),
+        },
+        _ => return Range::Full,
+    };
+    match start < len {
+        true => Range::Partial(start, end),
+        false => Range::Unsatisfiable,
+    }
+}
+
+/// Guess the content type from the extension of `path`.
+///
+/// The extension of an encoded sibling is skipped.
+fn content_type(path: &Path, encoded: bool) -> &'static str {
+    let path = match encoded {
+        true => path.file_stem().map(Path::new).unwrap_or(path),
+        false => path,
+    };
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "json" | "map" => "application/json",
+        "txt" => "text/plain; charset=utf-8",
+        "csv" => "text/csv; charset=utf-8",
+        "md" => "text/markdown; charset=utf-8",
+        "xml" => "application/xml",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "ico" => "image/x-icon",
+        "wasm" => "application/wasm",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "mp3" => "audio/mpeg",
+        "ogg" => "audio/ogg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use http::header;
+    use http::Method;
+    use http::Request;
+    use http::Response;
+    use http::StatusCode;
+
+    use super::parse_range;
+    use super::resolve;
+    use super::Range;
+    use super::ServeDir;
+    use crate::error::Error;
+    use crate::exec::run;
+    use crate::macros::param;
+    use crate::router::Router;
+
+    /// A directory removed on drop.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let path = std::env::temp_dir().join(format!("tackt-{}-{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&path);
+            std::fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+
+        fn write(&self, name: &str, content: &str) -> &TempDir {
+            let path = self.0.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+            self
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn req(path: &'static str) -> http::request::Builder {
+        Request::builder().uri(path)
+    }
+
+    fn get(dir: &TempDir, req: http::request::Builder) -> Result<Response<Vec<u8>>, Error> {
+        run(ServeDir::new(dir.path()), req.body(()).unwrap())
+    }
+
+    fn body(res: Result<Response<Vec<u8>>, Error>) -> (StatusCode, String) {
+        let res = res.unwrap();
+        (res.status(), String::from_utf8(res.into_body()).unwrap())
+    }
+
+    #[test]
+    fn serve() {
+        let dir = TempDir::new("serve");
+        dir.write("index.html", "home")
+            .write("app.js", "app")
+            .write("docs/index.html", "docs")
+            .write("a b.txt", "space");
+
+        let res = get(&dir, req("/app.js")).unwrap();
+        assert_eq!(
+            res.headers()[header::CONTENT_TYPE],
+            "text/javascript; charset=utf-8"
+        );
+        assert_eq!(res.headers()[header::CONTENT_LENGTH], "3");
+        assert_eq!(body(Ok(res)), (StatusCode::OK, "app".to_string()));
+
+        assert_eq!(body(get(&dir, req("/"))).1, "home");
+        assert_eq!(body(get(&dir, req("/docs"))).1, "docs");
+        assert_eq!(body(get(&dir, req("/docs/"))).1, "docs");
+        assert_eq!(body(get(&dir, req("/a%20b.txt"))).1, "space");
+
+        let res = get(&dir, req("/app.js").method(Method::HEAD)).unwrap();
+        assert_eq!(res.headers()[header::CONTENT_LENGTH], "3");
+        assert!(res.into_body().is_empty());
+
+        let res = get(&dir, req("/missing.js"));
+        assert_eq!(res.unwrap_err(), Error::Path);
+
+        let res = get(&dir, req("/app.js").method(Method::POST));
+        assert_eq!(res.unwrap_err(), Error::Method);
+    }
+
+    #[test]
+    fn traversal() {
+        let root = Path::new("/srv");
+        assert_eq!(resolve(root, "/a/./b//c"), Some(root.join("a/b/c")));
+        assert_eq!(resolve(root, ""), Some(root.to_path_buf()));
+        assert_eq!(resolve(root, "/../etc/passwd"), None);
+        assert_eq!(resolve(root, "/a/%2e%2e/%2e%2e/etc"), None);
+        assert_eq!(resolve(root, "/a%2f..%2f..%2fetc"), None);
+        assert_eq!(resolve(root, "/a%5c..%5cetc"), None);
+        assert_eq!(resolve(root, "/%ff"), None);
+        assert_eq!(resolve(root, "/%2"), None);
+        assert_eq!(resolve(root, "relative"), None);
+    }
+
+    #[test]
+    fn conditional() {
+        let dir = TempDir::new("conditional");
+        dir.write("app.css", "body {}");
+
+        let res = get(&dir, req("/app.css")).unwrap();
+        let etag = res.headers()[header::ETAG].clone();
+        let last_modified = res.headers()[header::LAST_MODIFIED].clone();
+
+        let res = get(
+            &dir,
+            req("/app.css").header(header::IF_NONE_MATCH, etag.clone()),
+        );
+        assert_eq!(body(res), (StatusCode::NOT_MODIFIED, String::new()));
+
+        let res = get(
+            &dir,
+            req("/app.css").header(header::IF_NONE_MATCH, "\"other\", *"),
+        );
+        assert_eq!(res.unwrap().status(), StatusCode::NOT_MODIFIED);
+
+        let res = get(
+            &dir,
+            req("/app.css").header(header::IF_NONE_MATCH, "\"other\""),
+        );
+        assert_eq!(body(res), (StatusCode::OK, "body {}".to_string()));
+
+        let res = get(
+            &dir,
+            req("/app.css").header(header::IF_MODIFIED_SINCE, last_modified),
+        );
+        assert_eq!(res.unwrap().status(), StatusCode::NOT_MODIFIED);
+
+        let old = "Sun, 06 Nov 1994 08:49:37 GMT";
+        let res = get(&dir, req("/app.css").header(header::IF_MODIFIED_SINCE, old));
+        assert_eq!(res.unwrap().status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn range() {
+        let dir = TempDir::new("range");
+        dir.write("data.txt", "0123456789");
+
+        let res = get(&dir, req("/data.txt").header(header::RANGE, "bytes=2-4")).unwrap();
+        assert_eq!(res.headers()[header::CONTENT_RANGE], "bytes 2-4/10");
+        assert_eq!(res.headers()[header::CONTENT_LENGTH], "3");
+        assert_eq!(
+            body(Ok(res)),
+            (StatusCode::PARTIAL_CONTENT, "234".to_string())
+        );
+
+        let res = get(&dir, req("/data.txt").header(header::RANGE, "bytes=-3"));
+        assert_eq!(body(res), (StatusCode::PARTIAL_CONTENT, "789".to_string()));
+
+        let res = get(&dir, req("/data.txt").header(header::RANGE, "bytes=20-")).unwrap();
+        assert_eq!(res.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(res.headers()[header::CONTENT_RANGE], "bytes */10");
+
+        let res = get(
+            &dir,
+            req("/data.txt")
+                .header(header::RANGE, "bytes=2-4")
+                .header(header::IF_RANGE, "\"stale\""),
+        );
+        assert_eq!(body(res), (StatusCode::OK, "0123456789".to_string()));
+
+        assert_eq!(parse_range("bytes=0-", 10), Range::Partial(0, 10));
+        assert_eq!(parse_range("bytes=8-20", 10), Range::Partial(8, 10));
+        assert_eq!(parse_range("bytes=-20", 10), Range::Partial(0, 10));
+        assert_eq!(parse_range("bytes=-0", 10), Range::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-0", 0), Range::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-1,4-5", 10), Range::Full);
+        assert_eq!(parse_range("bytes=4-1", 10), Range::Full);
+        assert_eq!(parse_range("lines=0-1", 10), Range::Full);
+    }
+
+    #[test]
+    fn precompressed() {
+        let dir = TempDir::new("precompressed");
+        dir.write("app.js", "plain")
+            .write("app.js.gz", "gzip")
+            .write("app.js.br", "br")
+            .write("only.js", "plain");
+        let serve = ServeDir::new(dir.path())
+            .precompressed_br()
+            .precompressed_gzip();
+        let get = |req: http::request::Builder| -> Result<Response<Vec<u8>>, Error> {
+            run(serve.clone(), req.body(()).unwrap())
+        };
+
+        let res = get(req("/app.js").header(header::ACCEPT_ENCODING, "gzip, br")).unwrap();
+        assert_eq!(res.headers()[header::CONTENT_ENCODING], "br");
+        assert_eq!(
+            res.headers()[header::CONTENT_TYPE],
+            "text/javascript; charset=utf-8"
+        );
+        assert_eq!(res.headers()[header::VARY], "accept-encoding");
+        assert_eq!(body(Ok(res)).1, "br");
+
+        let res = get(req("/app.js").header(header::ACCEPT_ENCODING, "gzip, br;q=0"));
+        assert_eq!(body(res).1, "gzip");
+
+        let res = get(req("/app.js"));
+        assert_eq!(body(res).1, "plain");
+
+        let res = get(req("/only.js").header(header::ACCEPT_ENCODING, "br")).unwrap();
+        assert!(!res.headers().contains_key(header::CONTENT_ENCODING));
+        assert_eq!(body(Ok(res)).1, "plain");
+
+        // not enabled.
+        let res = run(
+            ServeDir::new(dir.path()),
+            req("/app.js")
+                .header(header::ACCEPT_ENCODING, "br")
+                .body(())
+                .unwrap(),
+        );
+        assert_eq!(body(res).1, "plain");
+    }
+
+    #[test]
+    fn max_size() {
+        let dir = TempDir::new("max_size");
+        dir.write("data.txt", "0123456789");
+        let serve = |req: http::request::Builder| {
+            let serve = ServeDir::new(dir.path()).max_size(4);
+            run(serve, req.body(()).unwrap())
+        };
+
+        let res: Result<Response<Vec<u8>>, Error> = serve(req("/data.txt"));
+        let res = res.unwrap();
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(res.headers()[header::ACCEPT_RANGES], "bytes");
+        assert_eq!(
+            body(Ok(res)).1,
+            "file is larger than 4 bytes, request it in ranges"
+        );
+
+        let res = serve(req("/data.txt").header(header::RANGE, "bytes=6-"));
+        assert_eq!(body(res), (StatusCode::PARTIAL_CONTENT, "6789".to_string()));
+    }
+
+    #[test]
+    fn spa() {
+        let dir = TempDir::new("spa");
+        dir.write("index.html", "app")
+            .write("app.js", "script")
+            .write("docs/index.html", "docs");
+        let serve = ServeDir::new(dir.path()).spa();
+        let get = |req: http::request::Builder| -> Result<Response<Vec<u8>>, Error> {
+            run(serve.clone(), req.body(()).unwrap())
+        };
+        let html = "text/html,application/xhtml+xml;q=0.9,*/*;q=0.8";
+
+        let res = get(req("/users/7").header(header::ACCEPT, html)).unwrap();
+        assert_eq!(
+            res.headers()[header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(body(Ok(res)), (StatusCode::OK, "app".to_string()));
+
+        let res = get(req("/users/7/edit.html").header(header::ACCEPT, html));
+        assert_eq!(body(res).1, "app");
+        let res = get(req("/users/john.doe").header(header::ACCEPT, html));
+        assert_eq!(body(res).1, "app");
+        let res = get(req("/releases/1.2").header(header::ACCEPT, html));
+        assert_eq!(body(res).1, "app");
+
+        // existing files are still served.
+        let res = get(req("/app.js").header(header::ACCEPT, html));
+        assert_eq!(body(res).1, "script");
+        let res = get(req("/docs/").header(header::ACCEPT, html));
+        assert_eq!(body(res).1, "docs");
+
+        // asset-like paths.
+        let res = get(req("/missing.js").header(header::ACCEPT, html));
+        assert_eq!(res.unwrap_err(), Error::Path);
+        let res = get(req("/theme.css").header(header::ACCEPT, html));
+        assert_eq!(res.unwrap_err(), Error::Path);
+
+        // not a navigation.
+        let res = get(req("/users/7").header(header::ACCEPT, "application/json"));
+        assert_eq!(res.unwrap_err(), Error::Path);
+        let res = get(req("/users/7").header(header::ACCEPT, "text/html;q=0"));
+        assert_eq!(res.unwrap_err(), Error::Path);
+        let res = get(req("/users/7"));
+        assert_eq!(res.unwrap_err(), Error::Path);
+        let res = get(req("/users/7")
+            .method(Method::HEAD)
+            .header(header::ACCEPT, html));
+        assert_eq!(res.unwrap_err(), Error::Path);
+
+        // not enabled.
+        let res = req("/users/7").header(header::ACCEPT, html);
+        let res: Result<Response<Vec<u8>>, Error> =
+            run(ServeDir::new(dir.path()), res.body(()).unwrap());
+        assert_eq!(res.unwrap_err(), Error::Path);
+    }
+
+    param!(Home, GET, "/");
+
+    async fn home(_: Request<()>, _: Home) -> Result<Response<Vec<u8>>, Error> {
+        Ok(Response::new(b"root".to_vec()))
+    }
+
+    #[test]
+    fn mount() {
+        let dir = TempDir::new("mount");
+        dir.write("app.js", "app");
+        let router = Router::new(home).mount("/assets", ServeDir::new(dir.path()));
+
+        let res = run(router.clone(), req("/assets/app.js").body(()).unwrap());
+        assert_eq!(body(res).1, "app");
+
+        let res = run(router.clone(), req("/").body(()).unwrap());
+        assert_eq!(body(res).1, "root");
+
+        let res = run(router, req("/assets/missing.js").body(()).unwrap());
+        assert_eq!(res.unwrap_err(), Error::Path);
+
+        let router = Router::new(home).mount("/app", ServeDir::new(dir.path()).spa());
+        dir.write("index.html", "app");
+
+        let res = req("/app/users/7").header(header::ACCEPT, "text/html");
+        let res = run(router.clone(), res.body(()).unwrap());
+        assert_eq!(body(res).1, "app");
+
+        let res = run(router, req("/").body(()).unwrap());
+        assert_eq!(body(res).1, "root");
+    }
+}
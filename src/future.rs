@@ -3,6 +3,8 @@ use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
 
+use tower_service::Service;
+
 #[derive(Clone, Copy, Debug)]
 #[pin_project::pin_project(project = ProjectedMaybe)]
 pub enum Maybe<F, O> {
@@ -55,3 +57,59 @@ where
         }
     }
 }
+
+/// Wait for a service to be ready, then call it.
+#[pin_project::pin_project(project = ProjectedOneshot)]
+pub enum Oneshot<S: Service<T>, T> {
+    NotReady(Option<(S, T)>),
+    Called(#[pin] S::Future),
+}
+
+impl<S: Service<T>, T> Oneshot<S, T> {
+    #[inline]
+    pub const fn new(svc: S, req: T) -> Oneshot<S, T> {
+        Oneshot::NotReady(Some((svc, req)))
+    }
+}
+
+impl<S: Service<T>, T> std::fmt::Debug for Oneshot<S, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Oneshot::NotReady(..) => f.debug_tuple("NotReady").finish(),
+            Oneshot::Called(..) => f.debug_tuple("Called").finish(),
+        }
+    }
+}
+
+impl<S: Service<T>, T> Future for Oneshot<S, T> {
+    type Output = Result<S::Response, S::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            match self.as_mut().project() {
+                ProjectedOneshot::NotReady(state) => {
+                    let (svc, _) = match state {
+                        None => unreachable!("polled after ready"),
+                        Some(state) => state,
+                    };
+                    match svc.poll_ready(cx) {
+                        Poll::Pending => return Poll::Pending,
+                        Poll::Ready(Err(err)) => {
+                            state.take();
+                            return Poll::Ready(Err(err));
+                        }
+                        Poll::Ready(Ok(())) => {
+                            let (mut svc, req) = match state.take() {
+                                None => unreachable!("polled after ready"),
+                                Some(state) => state,
+                            };
+                            let fut = svc.call(req);
+                            self.set(Oneshot::Called(fut));
+                        }
+                    }
+                }
+                ProjectedOneshot::Called(fut) => return fut.poll(cx),
+            }
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use std::future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use http::Response;
+use tower_service::Service;
+
+use crate::error::Error;
+use crate::extract::FromRequest;
+use crate::extract::FromRequestParts;
+use crate::future::Maybe;
+use crate::param::Param;
+use crate::response::IntoResponse;
+use crate::route::Route;
+
+/// Wrap a handler function into a route.
+///
+/// A handler is like [`Func`][1], but its output is converted with
+/// [`IntoResponse`] instead of being returned as is, and it may take several
+/// extracted arguments:
+///
+/// ```text
+/// async fn handler(parts.., body, param) -> impl IntoResponse
+/// ```
+///
+/// where each `parts` implements [`FromRequestParts`], `body` implements
+/// [`FromRequest`] and `param` implements [`Param`]. The request itself is a
+/// valid `body`, so `fn(T, P)` is a handler too. Up to 8 `parts` arguments
+/// are supported.
+///
+/// Note that application code cannot construct this struct directly. This is
+/// exported for type annotation only.
+///
+/// [1]: crate::func::Func
+pub struct Handler<F, A, B> {
+    inner: F,
+
+    args: PhantomData<fn() -> (A, B)>,
+}
+
+impl<F, A, B> std::fmt::Debug for Handler<F, A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handler").finish()
+    }
+}
+
+impl<F, A, B> Handler<F, A, B> {
+    #[inline]
+    pub(crate) fn new(inner: F) -> Handler<F, A, B> {
+        Handler {
+            inner,
+            args: PhantomData,
+        }
+    }
+}
+
+impl<F: Copy, A, B> Copy for Handler<F, A, B> {}
+
+impl<F: Clone, A, B> Clone for Handler<F, A, B> {
+    fn clone(&self) -> Self {
+        Handler {
+            inner: self.inner.clone(),
+            args: self.args,
+        }
+    }
+}
+
+/// Call a handler with its extracted arguments.
+pub trait Call<H, X, P> {
+    type Future;
+
+    fn call(&mut self, head: H, body: X, param: P) -> Self::Future;
+}
+
+macro_rules! handler {
+    ($($H:ident),*) => {
+        impl<F, Fut, $($H,)* X, P> Call<($($H,)*), X, P> for F
+        where
+            F: FnMut($($H,)* X, P) -> Fut,
+        {
+            type Future = Fut;
+
+            #[inline]
+            #[allow(non_snake_case)]
+            fn call(&mut self, ($($H,)*): ($($H,)*), body: X, param: P) -> Fut {
+                self($($H,)* body, param)
+            }
+        }
+
+        impl<F, Fut, T, B, M, $($H,)* X, P> Service<T> for Handler<F, (M, $($H,)* X, P), B>
+        where
+            F: FnMut($($H,)* X, P) -> Fut + Clone,
+            $(
+                $H: FromRequestParts<T>,
+                $H::Rejection: IntoResponse<B>,
+            )*
+            X: FromRequest<T, M>,
+            X::Rejection: IntoResponse<B>,
+            P: Param<T>,
+            Fut: future::Future,
+            Fut::Output: IntoResponse<B>,
+        {
+            type Response = Response<B>;
+
+            type Error = Error;
+
+            type Future = Maybe<Future<F, ($($H,)*), P, X::Future, Fut, B>, Result<Response<B>, Error>>;
+
+            #[inline]
+            fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                Poll::Ready(Ok(()))
+            }
+
+            #[inline]
+            fn call(&mut self, req: T) -> Self::Future {
+                match self.param(&req) {
+                    Ok(param) => self.call_with_param(req, param),
+                    Err(err) => Maybe::ready(Err(err)),
+                }
+            }
+        }
+
+        impl<F, Fut, T, B, M, $($H,)* X, P> Route<T> for Handler<F, (M, $($H,)* X, P), B>
+        where
+            F: FnMut($($H,)* X, P) -> Fut + Clone,
+            $(
+                $H: FromRequestParts<T>,
+                $H::Rejection: IntoResponse<B>,
+            )*
+            X: FromRequest<T, M>,
+            X::Rejection: IntoResponse<B>,
+            P: Param<T>,
+            Fut: future::Future,
+            Fut::Output: IntoResponse<B>,
+        {
+            type Param = P;
+
+            #[inline]
+            #[allow(non_snake_case)]
+            fn call_with_param(&mut self, req: T, param: Self::Param) -> Self::Future {
+                $(
+                    let $H = match $H::from_request_parts(&req) {
+                        Ok(value) => value,
+                        Err(rejection) => return Maybe::ready(Ok(rejection.into_response())),
+                    };
+                )*
+                Maybe::Future(Future::new(
+                    X::from_request(req),
+                    self.inner.clone(),
+                    ($($H,)*),
+                    param,
+                ))
+            }
+        }
+    };
+}
+
+handler!();
+handler!(H1);
+handler!(H1, H2);
+handler!(H1, H2, H3);
+handler!(H1, H2, H3, H4);
+handler!(H1, H2, H3, H4, H5);
+handler!(H1, H2, H3, H4, H5, H6);
+handler!(H1, H2, H3, H4, H5, H6, H7);
+handler!(H1, H2, H3, H4, H5, H6, H7, H8);
+
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct Future<F, H, P, XF, Fut, B> {
+    #[pin]
+    state: State<F, H, P, XF, Fut>,
+
+    body: PhantomData<fn() -> B>,
+}
+
+impl<F, H, P, XF, Fut, B> Future<F, H, P, XF, Fut, B> {
+    #[inline]
+    fn new(fut: XF, func: F, head: H, param: P) -> Future<F, H, P, XF, Fut, B> {
+        Future {
+            state: State::Extract {
+                fut,
+                call: Some((func, head, param)),
+            },
+            body: PhantomData,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[pin_project::pin_project(project = ProjectedState)]
+enum State<F, H, P, XF, Fut> {
+    Extract {
+        #[pin]
+        fut: XF,
+        call: Option<(F, H, P)>,
+    },
+    Call {
+        #[pin]
+        fut: Fut,
+    },
+    Done,
+}
+
+impl<F, H, P, XF, X, R, Fut, B> future::Future for Future<F, H, P, XF, Fut, B>
+where
+    F: Call<H, X, P, Future = Fut>,
+    XF: future::Future<Output = Result<X, R>>,
+    R: IntoResponse<B>,
+    Fut: future::Future,
+    Fut::Output: IntoResponse<B>,
+{
+    type Output = Result<Response<B>, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.project().state;
+        loop {
+            match state.as_mut().project() {
+                ProjectedState::Extract { fut, call } => {
+                    let body = match fut.poll(cx) {
+                        Poll::Pending => return Poll::Pending,
+                        Poll::Ready(body) => body,
+                    };
+                    let (mut func, head, param) = match call.take() {
+                        None => unreachable!("polled after ready"),
+                        Some(call) => call,
+                    };
+                    match body {
+                        Err(rejection) => {
+                            state.set(State::Done);
+                            return Poll::Ready(Ok(rejection.into_response()));
+                        }
+                        Ok(body) => {
+                            let fut = func.call(head, body, param);
+                            state.set(State::Call { fut });
+                        }
+                    }
+                }
+                ProjectedState::Call { fut } => {
+                    let out = match fut.poll(cx) {
+                        Poll::Pending => return Poll::Pending,
+                        Poll::Ready(out) => out,
+                    };
+                    state.set(State::Done);
+                    return Poll::Ready(Ok(out.into_response()));
+                }
+                ProjectedState::Done => unreachable!("polled after ready"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Request;
+    use http::Response;
+    use http::StatusCode;
+
+    use crate::exec::run;
+    use crate::macros::param;
+    use crate::router::Router;
+
+    param!(Home, GET, "/");
+    param!(Create, POST, "/");
+
+    async fn home(_: Request<()>, _: Home) -> &'static str {
+        "home"
+    }
+
+    async fn create(_: Request<()>, _: Create) -> Result<(StatusCode, String), StatusCode> {
+        Ok((StatusCode::CREATED, "created".to_string()))
+    }
+
+    fn req(method: http::Method, path: &'static str) -> Request<()> {
+        Request::builder()
+            .method(method)
+            .uri(http::Uri::from_static(path))
+            .body(())
+            .unwrap()
+    }
+
+    #[test]
+    fn test() {
+        let router = Router::handler(home).handle(create);
+
+        let res: Response<String> = run(router, req(http::Method::GET, "/")).unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.body(), "home");
+
+        let res = run(router, req(http::Method::POST, "/")).unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert_eq!(res.body(), "created");
+
+        let res = run(router, req(http::Method::GET, "/nowhere"));
+        assert_eq!(res.err(), Some(crate::Error::Path));
+    }
+}
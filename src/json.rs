@@ -0,0 +1,151 @@
+use http::header;
+use http::HeaderValue;
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use http_body::Body;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::extract::Collect;
+use crate::extract::FromRequest;
+use crate::extract::Rejection;
+use crate::response::IntoResponse;
+
+/// JSON request body or response.
+///
+/// As a response, the inner value is serialized with [`serde_json`][1] and
+/// the response gets `Content-Type: application/json`. A value that fails to
+/// serialize becomes a `500 Internal Server Error` response.
+///
+/// As a handler argument, the request body is deserialized. The request must
+/// have a JSON `Content-Type`.
+///
+/// [1]: https://docs.rs/serde_json/1
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Json<T>(pub T);
+
+impl<T, B> IntoResponse<B> for Json<T>
+where
+    T: Serialize,
+    B: From<Vec<u8>>,
+{
+    fn into_response(self) -> Response<B> {
+        match serde_json::to_vec(&self.0) {
+            Ok(body) => {
+                let mut res = Response::new(B::from(body));
+                res.headers_mut().insert(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static("application/json"),
+                );
+                res
+            }
+            Err(err) => {
+                let mut res = Response::new(B::from(err.to_string().into_bytes()));
+                *res.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+                res
+            }
+        }
+    }
+}
+
+impl<T, B> FromRequest<Request<B>> for Json<T>
+where
+    T: DeserializeOwned,
+    B: Body,
+    B::Error: std::fmt::Display,
+{
+    type Rejection = Rejection;
+
+    type Future = Collect<B, Json<T>>;
+
+    fn from_request(req: Request<B>) -> Self::Future {
+        if !is_json(&req) {
+            return Collect::reject(req.into_body(), Rejection::UnsupportedMediaType);
+        }
+        Collect::new(req, |body| {
+            serde_json::from_slice(&body)
+                .map(Json)
+                .map_err(|err| Rejection::InvalidJson(err.to_string()))
+        })
+    }
+}
+
+fn is_json<B>(req: &Request<B>) -> bool {
+    let mime = match req.headers().get(header::CONTENT_TYPE) {
+        None => return false,
+        Some(value) => match value.to_str() {
+            Err(..) => return false,
+            Ok(value) => value,
+        },
+    };
+    let mime = mime.split(';').next().unwrap_or("").trim();
+    match mime.split_once('/') {
+        Some((typ, sub)) => {
+            typ.eq_ignore_ascii_case("application")
+                && (sub.eq_ignore_ascii_case("json")
+                    || sub.len() > 5 && sub[sub.len() - 5..].eq_ignore_ascii_case("+json"))
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use http::Request;
+    use http::Response;
+    use http::StatusCode;
+
+    use super::Json;
+    use crate::exec::oneshot;
+    use crate::extract::FromRequest;
+    use crate::extract::Rejection;
+    use crate::response::IntoResponse;
+
+    fn req(mime: &'static str, body: &'static str) -> Request<String> {
+        Request::builder()
+            .header("content-type", mime)
+            .body(body.to_string())
+            .unwrap()
+    }
+
+    #[test]
+    fn from_request() {
+        let res = oneshot(Json::<BTreeMap<String, i32>>::from_request(req(
+            "application/json; charset=utf-8",
+            r#"{"id":1}"#,
+        )));
+        assert_eq!(res, Ok(Json(BTreeMap::from([("id".to_string(), 1)]))));
+
+        let res = oneshot(Json::<BTreeMap<String, i32>>::from_request(req(
+            "application/problem+json",
+            r#"{"id":1}"#,
+        )));
+        assert!(res.is_ok());
+
+        let res = oneshot(Json::<BTreeMap<String, i32>>::from_request(req(
+            "text/plain",
+            r#"{"id":1}"#,
+        )));
+        assert_eq!(res, Err(Rejection::UnsupportedMediaType));
+
+        let res = oneshot(Json::<BTreeMap<String, i32>>::from_request(req(
+            "application/json",
+            r#"{"id":"#,
+        )));
+        assert!(matches!(res, Err(Rejection::InvalidJson(..))));
+    }
+
+    #[test]
+    fn into_response() {
+        let res: Response<Vec<u8>> = Json(BTreeMap::from([("id", 1)])).into_response();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()["content-type"], "application/json");
+        assert_eq!(res.body(), br#"{"id":1}"#);
+
+        let res: Response<Vec<u8>> = Json(BTreeMap::from([((), 1)])).into_response();
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,204 @@
+use std::task::Context;
+use std::task::Poll;
+
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::error::Error;
+use crate::future::Maybe;
+use crate::future::Oneshot;
+use crate::param;
+use crate::priority::Priority;
+use crate::route::Route;
+
+/// A route wrapped by a [`Layer`].
+///
+/// The layer only wraps the handling of a request, not the matching. A
+/// request that does not match the inner route never reaches the layer, so a
+/// layered route takes part in routing like any other route.
+///
+/// The layered service is cloned for every request and driven to readiness
+/// before it is called.
+///
+/// Note that application code cannot construct this struct directly. This is
+/// exported for type annotation only.
+#[derive(Clone, Copy, Debug)]
+pub struct Layered<R, S> {
+    route: R,
+    service: S,
+}
+
+impl<R, S> Layered<R, S> {
+    #[inline]
+    pub(crate) fn new<L>(route: R, layer: L) -> Layered<R, S>
+    where
+        R: Clone,
+        L: Layer<R, Service = S>,
+    {
+        let service = layer.layer(route.clone());
+        Layered { route, service }
+    }
+}
+
+impl<R, S, T> Service<T> for Layered<R, S>
+where
+    R: Route<T>,
+    R::Error: From<Error>,
+    S: Clone + Service<T, Response = R::Response, Error = R::Error>,
+{
+    type Response = R::Response;
+
+    type Error = R::Error;
+
+    type Future = Maybe<Oneshot<S, T>, Result<Self::Response, Self::Error>>;
+
+    #[inline]
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn call(&mut self, req: T) -> Self::Future {
+        match self.param(&req) {
+            Ok(param) => self.call_with_param(req, param),
+            Err(err) => Maybe::ready(Err(err.into())),
+        }
+    }
+}
+
+impl<R, S, T> Route<T> for Layered<R, S>
+where
+    R: Route<T>,
+    R::Error: From<Error>,
+    S: Clone + Service<T, Response = R::Response, Error = R::Error>,
+{
+    type Param = Param;
+
+    #[inline]
+    fn call_with_param(&mut self, req: T, _: Self::Param) -> Self::Future {
+        Maybe::Future(Oneshot::new(self.service.clone(), req))
+    }
+
+    #[inline]
+    fn param(&self, req: &T) -> Result<Self::Param, Error> {
+        self.route
+            .param(req)
+            .map(|param| Param(param::Param::<T>::priority(&param)))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Param(Priority);
+
+impl<T> param::Param<T> for Param {
+    fn from_request(_: &T) -> Result<Self, Error> {
+        panic!("BUG: layered should call param from Route trait.");
+    }
+
+    #[inline]
+    fn priority(&self) -> Priority {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::task::Context;
+    use std::task::Poll;
+
+    use http::Request;
+    use tower_layer::Layer;
+    use tower_service::Service;
+
+    use crate::error::Error;
+    use crate::exec::run;
+    use crate::macros::param;
+    use crate::router::Router;
+
+    #[derive(Clone)]
+    struct Count(Arc<AtomicUsize>);
+
+    impl<S> Layer<S> for Count {
+        type Service = Counted<S>;
+
+        fn layer(&self, inner: S) -> Self::Service {
+            Counted {
+                inner,
+                count: self.0.clone(),
+                ready: false,
+            }
+        }
+    }
+
+    #[derive(Clone)]
+    struct Counted<S> {
+        inner: S,
+        count: Arc<AtomicUsize>,
+        ready: bool,
+    }
+
+    impl<S: Service<T>, T> Service<T> for Counted<S> {
+        type Response = S::Response;
+
+        type Error = S::Error;
+
+        type Future = S::Future;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.ready = true;
+            self.inner.poll_ready(cx)
+        }
+
+        fn call(&mut self, req: T) -> Self::Future {
+            assert!(self.ready, "called before ready");
+            self.ready = false;
+            self.count.fetch_add(1, Ordering::SeqCst);
+            self.inner.call(req)
+        }
+    }
+
+    param!(Home, GET, "/");
+    param!(About, GET, "/about");
+
+    async fn home(_: Request<()>, _: Home) -> Result<&'static str, Error> {
+        Ok("home")
+    }
+
+    async fn about(_: Request<()>, _: About) -> Result<&'static str, Error> {
+        Ok("about")
+    }
+
+    fn req(path: &'static str) -> Request<()> {
+        Request::builder()
+            .method(http::Method::GET)
+            .uri(http::Uri::from_static(path))
+            .body(())
+            .unwrap()
+    }
+
+    #[test]
+    fn test() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let router = Router::new(home).merge(Router::new(about).layer(Count(count.clone())));
+
+        let res = run(router.clone(), req("/"));
+        assert_eq!(res, Ok("home"));
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+
+        let res = run(router.clone(), req("/about"));
+        assert_eq!(res, Ok("about"));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        let res = run(router.clone(), req("/nowhere"));
+        assert_eq!(res, Err(Error::Path));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        let router = router.layer(Count(count.clone()));
+        let res = run(router.clone(), req("/about"));
+        assert_eq!(res, Ok("about"));
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+    }
+}
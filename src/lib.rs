@@ -26,6 +26,34 @@
 //!
 //! **NOTE**: `#[route]` attribute changes the function signature.
 //!
+//! ## handlers
+//!
+//! With the `response` feature (enabled by default), a route may take
+//! [extracted arguments][FromRequestParts] and return anything that implements
+//! [`IntoResponse`] by registering it with [`Router::handler`] and
+//! [`Router::handle`]. [`Router::respond`] then turns routing errors into
+//! `404` and `405` responses.
+//!
+//! ```rust
+//! # #[cfg(feature = "response")] {
+//! use http::HeaderMap;
+//! use http::StatusCode;
+//! use tackt::route;
+//! use tackt::Router;
+//!
+//! #[route(GET: "entity" / id)]
+//! async fn entity(
+//!     headers: HeaderMap,
+//!     _: http::Request<hyper::Body>,
+//!     id: i32,
+//! ) -> (StatusCode, String) {
+//!     (StatusCode::OK, format!("entity: {id} {}", headers.len()))
+//! }
+//!
+//! let router = Router::handler(entity).respond::<_, hyper::Body>();
+//! # }
+//! ```
+//!
 //! ## route spec examples
 //!
 //! 1.  Empty
@@ -60,15 +88,82 @@
 //!     #[route(GET: "path" / "to" / "somewhere")]
 //!     ```
 //!
+//!     With a single method, the shorthand attribute is the same:
+//!
+//!     ```rust,ignore
+//!     #[get("path" / "to" / "somewhere")]
+//!     ```
+//!
+//! 1.  Extension methods
+//!
+//!     Methods other than the associated constants of [`Method`] are written
+//!     as string literals. `ANY` matches any method.
+//!
+//!     ```rust,ignore
+//!     #[route("PROPFIND", "MKCOL": "dav" / path*)]
+//!     #[route(ANY: "proxy" / path*)]
+//!     ```
+//!
+//! 1.  Literals with slashes
+//!
+//!     A literal containing `/` is split into several segments, so these are
+//!     the same.
+//!
+//!     ```rust,ignore
+//!     #[get("api/v1" / "users" / id)]
+//!     #[get("api" / "v1" / "users" / id)]
+//!     ```
+//!
+//! 1.  Params without copying
+//!
+//!     Params and wildcards are parsed with [`FromSegment`]. A [`Capture`]
+//!     shares the request's path instead of copying the segment.
+//!
+//!     ```rust,ignore
+//!     #[route(ANY: "proxy" / path*)]
+//!     async fn proxy(req: Request<Body>, path: Capture) -> Result<Response<Body>, Error> {
+//!         todo!()
+//!     }
+//!     ```
+//!
+//! 1.  Priority
+//!
+//!     When several routes match a request, the most specific one is called,
+//!     regardless of the order the routes are added. A literal is more
+//!     specific than a param, and a param is more specific than a wildcard.
+//!     The specificity can be overridden with `priority`, see [`Priority`].
+//!
+//!     ```rust,ignore
+//!     #[get("user" / "me")]                // called for "/user/me"
+//!     #[get("user" / id)]                  // called for "/user/1"
+//!     #[get("user" / path*, priority = 1)] // called for anything
+//!     ```
+//!
+//! 1.  Case-insensitive literals
+//!
+//!     `ignore_ascii_case` compares the literals ignoring ASCII case, while
+//!     `ignore_case` compares their lowercase. Params keep their casing.
+//!
+//!     ```rust,ignore
+//!     #[get("user" / name, ignore_ascii_case)] // matches "/USER/John"
+//!     ```
+//!
+//!     Note that the path of an [`http::Request`] is percent-encoded, so
+//!     `ignore_case` only differs from `ignore_ascii_case` for requests that
+//!     are not.
+//!
 //! ## route syntax:
 //!
 //! ```text
-//! spec: methods ':' segments
-//!     / methods
-//!     / segments
+//! spec: methods ':' segments [',' options]
+//!     / methods [',' options]
+//!     / segments [',' options]
+//!     / options
 //!     / empty
 //!
-//! methods: identifier [',' identifier]*
+//! methods: method [',' method]*
+//!
+//! method: identifier / literal-str / 'ANY'
 //!
 //! segments: segment ['/' segment]* ['/' rest]
 //!
@@ -76,6 +171,12 @@
 //!
 //! rest: identifier '*'
 //!
+//! options: option [',' option]*
+//!
+//! option: 'priority' '=' ['-'] integer
+//!     / 'ignore_ascii_case'
+//!     / 'ignore_case'
+//!
 //! empty:
 //! ```
 //!
@@ -88,16 +189,39 @@
 )]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+mod around;
+mod boxed;
 mod error;
+#[cfg(feature = "response")]
+mod extract;
+#[cfg(feature = "fs")]
+mod fs;
 mod func;
 mod future;
+#[cfg(feature = "response")]
+mod handler;
+#[cfg(feature = "json")]
+mod json;
+mod layer;
+mod list;
 mod macros;
+mod map;
 mod mount;
 mod or;
 mod param;
+mod priority;
+#[cfg(feature = "query")]
+mod query;
+mod redirect;
 mod request;
+#[cfg(feature = "response")]
+mod response;
 mod route;
 mod router;
+mod segment;
+#[cfg(feature = "response")]
+mod state;
+mod swap;
 mod void;
 mod with;
 
@@ -106,21 +230,99 @@ mod exec;
 
 pub use error::Error;
 pub use param::Param;
+pub use priority::Priority;
 pub use route::Route;
 pub use router::Router;
+pub use segment::Capture;
+pub use segment::FromSegment;
 pub use void::Void;
 
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+pub use request::ExtensionReq;
 pub use request::MethodReq;
 pub use request::PathReq;
 pub use request::RemovePrefix;
 
+pub use around::Around;
+pub use around::Next;
+pub use boxed::BoxRoute;
+pub use boxed::BoxRouter;
+pub use boxed::UnsyncBoxRoute;
+pub use boxed::UnsyncBoxRouter;
 pub use func::Func;
+pub use layer::Layered;
+pub use map::MapErr;
+pub use map::MapResponse;
 pub use mount::Mount;
 pub use or::Or;
+pub use redirect::Redirect;
+pub use swap::SwapRouter;
+pub use with::With;
+pub use with::WithService;
+
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+pub use extract::BodyLimit;
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+pub use extract::Extension;
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+pub use extract::FromRequest;
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+pub use extract::FromRequestParts;
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+pub use extract::Rejection;
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+pub use handler::Handler;
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+pub use response::IntoResponse;
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+pub use response::Respond;
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+pub use state::State;
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+pub use state::WithState;
+
+#[cfg(feature = "fs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fs")))]
+pub use fs::ServeDir;
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub use json::Json;
+#[cfg(feature = "query")]
+#[cfg_attr(docsrs, doc(cfg(feature = "query")))]
+pub use query::Query;
 
 pub use http::Method;
+pub use tower_layer::Layer;
 pub use tower_service::Service;
 
+/// Items used by the generated code of the macros, not public API.
+#[doc(hidden)]
+#[cfg(feature = "macros")]
+pub mod __private {
+    /// The types of a `Result`, whatever alias it is written with, like
+    /// `io::Result<T>`.
+    pub trait ResultParts {
+        type Ok;
+        type Err;
+    }
+
+    impl<T, E> ResultParts for Result<T, E> {
+        type Ok = T;
+        type Err = E;
+    }
+}
+
 /// The attribute to describe route's spec.
 ///
 /// See [the top-level documentation][lib].
@@ -130,7 +332,153 @@ pub use tower_service::Service;
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 pub use tackt_macros::route;
 
-/// Derive [`Param`][crate::param::Param] for struct.
+/// Prepend a prefix to every route spec in a module or an `impl` block.
+///
+/// The prefix only has literals and params. It applies to `#[route]`, the
+/// method shorthands like [`#[get]`][macro@get], and `#[derive(Param)]` specs:
+///
+/// ```rust
+/// #[tackt::route_prefix("api" / "v1")]
+/// mod api {
+///     use tackt::get;
+///
+///     // matches `GET /api/v1/user/:id`
+///     #[get("user" / id)]
+///     pub async fn user(_: http::Request<()>, id: u64) -> Result<String, tackt::Error> {
+///         Ok(format!("user {id}"))
+///     }
+/// }
+/// ```
+///
+/// A nested module with its own prefix is prefixed by the outer one.
+///
+/// Options after the prefix, like `ignore_ascii_case`, apply to every route
+/// that does not set them:
+///
+/// ```rust,ignore
+/// #[tackt::route_prefix("legacy", ignore_ascii_case)]
+/// mod legacy {
+///     // matches `GET /Legacy/Login`
+///     #[get("login")]
+///     pub async fn login(_: http::Request<()>) -> Result<String, tackt::Error> {
+///         Ok("login".to_string())
+///     }
+/// }
+/// ```
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use tackt_macros::route_prefix;
+
+/// Shorthand for `#[route(GET: ...)]`.
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use tackt_macros::get;
+
+/// Shorthand for `#[route(POST: ...)]`.
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use tackt_macros::post;
+
+/// Shorthand for `#[route(PUT: ...)]`.
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use tackt_macros::put;
+
+/// Shorthand for `#[route(DELETE: ...)]`.
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use tackt_macros::delete;
+
+/// Shorthand for `#[route(PATCH: ...)]`.
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use tackt_macros::patch;
+
+/// Shorthand for `#[route(HEAD: ...)]`.
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use tackt_macros::head;
+
+/// Shorthand for `#[route(OPTIONS: ...)]`.
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use tackt_macros::options;
+
+/// Shorthand for `#[route(TRACE: ...)]`.
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use tackt_macros::trace;
+
+/// Turn the `#[route]` methods of an `impl` block into routes.
+///
+/// Each route method takes `&self`, the request, and the route params. The
+/// controller gets a `router(self)` method that shares the controller between
+/// its routes with an [`Arc`][std::sync::Arc]:
+///
+/// ```rust
+/// use tackt::controller;
+///
+/// struct UserController {
+///     prefix: String,
+/// }
+///
+/// #[controller]
+/// impl UserController {
+///     #[route(GET: "user" / id)]
+///     async fn show(&self, _: http::Request<hyper::Body>, id: u64) -> Result<String, tackt::Error> {
+///         Ok(format!("{} {id}", self.prefix))
+///     }
+/// }
+///
+/// let controller = UserController { prefix: "user".to_string() };
+/// let router = controller.router();
+/// ```
+///
+/// When more than one route matches, the one with the highest [`Priority`]
+/// is called, or the first declared one of them. The routes must share the
+/// same request, response, and error types. A route method returns a
+/// `Result`, possibly through an alias like `io::Result<T>`. The `impl` block
+/// cannot be generic.
+///
+/// This attribute is named `controller` rather than `routes`, since the
+/// [`routes!`] macro already takes that name.
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use tackt_macros::controller;
+
+/// Derive [`Param`][crate::param::Param] for struct or enum.
+///
+/// Each variant of an enum has its own `#[route]` spec, so one route can
+/// handle several specs. When more than one variant matches, the one with the
+/// highest [`Priority`] is returned, or the first declared one of them, like
+/// [`Router::route`] does:
+///
+/// ```rust
+/// # use tackt::Param;
+/// #[derive(Param)]
+/// enum UserRoute {
+///     #[route(GET: "user")]
+///     List,
+///     #[route(GET: "user" / id)]
+///     Show { id: u64 },
+///     #[route(DELETE: "user" / id)]
+///     Delete { id: u64 },
+/// }
+/// ```
+///
+/// Tuple fields are bound to the params in the order they appear in the spec,
+/// while a unit struct or variant matches a spec without params:
+///
+/// ```rust
+/// # use tackt::Param;
+/// #[derive(Param)]
+/// #[route(GET: "item" / id)]
+/// struct Item(u64);
+///
+/// #[derive(Param)]
+/// #[route(GET: "health")]
+/// struct Health;
+/// ```
 ///
 /// See [`Param` doc][crate::param::Param].
 #[cfg(feature = "macros")]
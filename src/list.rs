@@ -0,0 +1,122 @@
+use tower_service::Service;
+
+use crate::error::Error;
+use crate::future::Maybe;
+use crate::param;
+use crate::priority::Priority;
+use crate::route::Route;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Param<P> {
+    index: usize,
+    param: P,
+}
+
+impl<P, T> param::Param<T> for Param<P>
+where
+    P: param::Param<T>,
+{
+    #[inline]
+    fn from_request(_: &T) -> Result<Self, Error> {
+        panic!("BUG: list should call param from Route trait.");
+    }
+
+    #[inline]
+    fn priority(&self) -> Priority {
+        self.param.priority()
+    }
+}
+
+/// Routing over a list of routes of the same type.
+///
+/// Routes are matched like a chain of [`Or`][crate::or::Or]: the matching route
+/// with the highest priority wins, or the first one of them.
+#[derive(Clone, Debug)]
+pub(crate) struct List<R> {
+    routes: Vec<R>,
+}
+
+impl<R> List<R> {
+    #[inline]
+    pub(crate) fn new(routes: Vec<R>) -> List<R> {
+        List { routes }
+    }
+}
+
+impl<R, T> Service<T> for List<R>
+where
+    R: Route<T>,
+    R::Error: From<Error>,
+{
+    type Response = R::Response;
+
+    type Error = R::Error;
+
+    type Future = Maybe<R::Future, Result<Self::Response, Self::Error>>;
+
+    /// Every route must be ready, since the route is only known when the
+    /// request is called.
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        use std::task::Poll;
+
+        let mut ready = true;
+        for route in self.routes.iter_mut() {
+            match route.poll_ready(cx) {
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Ready(Ok(())) => {}
+                Poll::Pending => ready = false,
+            }
+        }
+        match ready {
+            true => Poll::Ready(Ok(())),
+            false => Poll::Pending,
+        }
+    }
+
+    fn call(&mut self, req: T) -> Self::Future {
+        match self.param(&req) {
+            Err(err) => Maybe::ready(Err(err.into())),
+            Ok(param) => self.call_with_param(req, param),
+        }
+    }
+}
+
+impl<R, T> Route<T> for List<R>
+where
+    R: Route<T>,
+    R::Error: From<Error>,
+{
+    type Param = Param<R::Param>;
+
+    fn call_with_param(&mut self, req: T, param: Self::Param) -> Self::Future {
+        Maybe::Future(self.routes[param.index].call_with_param(req, param.param))
+    }
+
+    fn param(&self, req: &T) -> Result<Self::Param, Error> {
+        use param::Param as _;
+
+        let mut found: Option<Param<R::Param>> = None;
+        let mut error = None;
+        for (index, route) in self.routes.iter().enumerate() {
+            match route.param(req) {
+                Ok(param) => {
+                    let higher = match &found {
+                        None => true,
+                        Some(prev) => param.priority().outranks(prev.param.priority()),
+                    };
+                    if higher {
+                        found = Some(Param { index, param });
+                    }
+                }
+                Err(err) => error = Some(error.map_or(err, |prev| std::cmp::min(prev, err))),
+            }
+        }
+        match found {
+            Some(param) => Ok(param),
+            None => Err(error.unwrap_or(Error::Path)),
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use std::future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use tower_service::Service;
+
+use crate::error::Error;
+use crate::route::Route;
+
+/// Map the response of a route.
+///
+/// Note that application code cannot construct this struct directly. This is
+/// exported for type annotation only.
+#[derive(Clone, Copy, Debug)]
+pub struct MapResponse<R, F> {
+    inner: R,
+    func: F,
+}
+
+impl<R, F> MapResponse<R, F> {
+    #[inline]
+    pub(crate) fn new<T, U>(inner: R, func: F) -> MapResponse<R, F>
+    where
+        R: Service<T>,
+        F: Clone + FnMut(R::Response) -> U,
+    {
+        MapResponse { inner, func }
+    }
+}
+
+impl<R, F, T, U> Service<T> for MapResponse<R, F>
+where
+    R: Service<T>,
+    F: Clone + FnMut(R::Response) -> U,
+{
+    type Response = U;
+
+    type Error = R::Error;
+
+    type Future = MapResponseFuture<R::Future, F>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    #[inline]
+    fn call(&mut self, req: T) -> Self::Future {
+        MapResponseFuture::new(self.inner.call(req), self.func.clone())
+    }
+}
+
+impl<R, F, T, U> Route<T> for MapResponse<R, F>
+where
+    R: Route<T>,
+    F: Clone + FnMut(R::Response) -> U,
+{
+    type Param = R::Param;
+
+    #[inline]
+    fn call_with_param(&mut self, req: T, param: Self::Param) -> Self::Future {
+        MapResponseFuture::new(self.inner.call_with_param(req, param), self.func.clone())
+    }
+
+    #[inline]
+    fn param(&self, req: &T) -> Result<Self::Param, Error> {
+        self.inner.param(req)
+    }
+}
+
+/// Map the error of a route.
+///
+/// Note that application code cannot construct this struct directly. This is
+/// exported for type annotation only.
+#[derive(Clone, Copy, Debug)]
+pub struct MapErr<R, F> {
+    inner: R,
+    func: F,
+}
+
+impl<R, F> MapErr<R, F> {
+    #[inline]
+    pub(crate) fn new<T, E>(inner: R, func: F) -> MapErr<R, F>
+    where
+        R: Service<T>,
+        F: Clone + FnMut(R::Error) -> E,
+    {
+        MapErr { inner, func }
+    }
+}
+
+impl<R, F, T, E> Service<T> for MapErr<R, F>
+where
+    R: Service<T>,
+    F: Clone + FnMut(R::Error) -> E,
+{
+    type Response = R::Response;
+
+    type Error = E;
+
+    type Future = MapErrFuture<R::Future, F>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(&mut self.func)
+    }
+
+    #[inline]
+    fn call(&mut self, req: T) -> Self::Future {
+        MapErrFuture::new(self.inner.call(req), self.func.clone())
+    }
+}
+
+impl<R, F, T, E> Route<T> for MapErr<R, F>
+where
+    R: Route<T>,
+    F: Clone + FnMut(R::Error) -> E,
+{
+    type Param = R::Param;
+
+    #[inline]
+    fn call_with_param(&mut self, req: T, param: Self::Param) -> Self::Future {
+        MapErrFuture::new(self.inner.call_with_param(req, param), self.func.clone())
+    }
+
+    #[inline]
+    fn param(&self, req: &T) -> Result<Self::Param, Error> {
+        self.inner.param(req)
+    }
+}
+
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct MapResponseFuture<Fut, F> {
+    #[pin]
+    fut: Fut,
+    func: F,
+}
+
+impl<Fut, F> MapResponseFuture<Fut, F> {
+    #[inline]
+    fn new(fut: Fut, func: F) -> MapResponseFuture<Fut, F> {
+        MapResponseFuture { fut, func }
+    }
+}
+
+impl<Fut, F, U, E, V> future::Future for MapResponseFuture<Fut, F>
+where
+    Fut: future::Future<Output = Result<U, E>>,
+    F: FnMut(U) -> V,
+{
+    type Output = Result<V, E>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        match this.fut.poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(out) => Poll::Ready(out.map(this.func)),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct MapErrFuture<Fut, F> {
+    #[pin]
+    fut: Fut,
+    func: F,
+}
+
+impl<Fut, F> MapErrFuture<Fut, F> {
+    #[inline]
+    fn new(fut: Fut, func: F) -> MapErrFuture<Fut, F> {
+        MapErrFuture { fut, func }
+    }
+}
+
+impl<Fut, F, U, E, V> future::Future for MapErrFuture<Fut, F>
+where
+    Fut: future::Future<Output = Result<U, E>>,
+    F: FnMut(E) -> V,
+{
+    type Output = Result<U, V>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        match this.fut.poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(out) => Poll::Ready(out.map_err(this.func)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Request;
+
+    use crate::error::Error;
+    use crate::exec::run;
+    use crate::macros::param;
+    use crate::router::Router;
+
+    param!(Home, GET, "/");
+    param!(About, GET, "/about");
+
+    async fn home(_: Request<()>, _: Home) -> Result<&'static str, Error> {
+        Ok("home")
+    }
+
+    async fn about(_: Request<()>, _: About) -> Result<usize, Wrapped> {
+        Ok(0)
+    }
+
+    fn req(path: &'static str) -> Request<()> {
+        Request::builder()
+            .method(http::Method::GET)
+            .uri(http::Uri::from_static(path))
+            .body(())
+            .unwrap()
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Wrapped(Error);
+
+    impl From<Error> for Wrapped {
+        fn from(err: Error) -> Self {
+            Wrapped(err)
+        }
+    }
+
+    #[test]
+    fn test() {
+        let router = Router::new(home)
+            .map_response(|res: &str| res.len())
+            .map_err(Wrapped)
+            .route(about);
+
+        let res = run(router, req("/"));
+        assert_eq!(res, Ok(4));
+
+        let res = run(router, req("/about"));
+        assert_eq!(res, Ok(0));
+
+        let res = run(router, req("/nowhere"));
+        assert_eq!(res, Err(Wrapped(Error::Path)));
+    }
+}
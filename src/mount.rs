@@ -43,9 +43,9 @@ where
     #[inline]
     fn poll_ready(
         &mut self,
-        _: &mut std::task::Context<'_>,
+        cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Result<(), Self::Error>> {
-        std::task::Poll::Ready(Ok(()))
+        self.inner.poll_ready(cx)
     }
 
     fn call(&mut self, req: T) -> Self::Future {
@@ -91,6 +91,7 @@ mod tests {
 
     use crate::error::Error;
     use crate::exec::run;
+    use crate::exec::Gate;
     use crate::macros::param;
     use crate::router::Router;
 
@@ -142,4 +143,17 @@ mod tests {
         let res = run(router, req("/r2/route2"));
         assert_eq!(res, Ok("route2"));
     }
+
+    #[test]
+    fn ready() {
+        let root = Router::new(root);
+        let r1 = Gate::new(Router::new(route1));
+        let router = root.mount("/r1", r1);
+
+        let res = run(router, req("/"));
+        assert_eq!(res, Ok("root"));
+
+        let res = run(router, req("/r1/route1"));
+        assert_eq!(res, Ok("route1"));
+    }
 }
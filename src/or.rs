@@ -4,6 +4,7 @@ use crate::error::Error;
 use crate::future::Either;
 use crate::future::Maybe;
 use crate::param;
+use crate::priority::Priority;
 use crate::route::Route;
 
 #[derive(Clone, Copy, Debug)]
@@ -21,6 +22,14 @@ where
     fn from_request(_: &T) -> Result<Self, Error> {
         panic!("BUG: or should call param from Route trait.");
     }
+
+    #[inline]
+    fn priority(&self) -> Priority {
+        match self {
+            Param::Left(param) => param.priority(),
+            Param::Right(param) => param.priority(),
+        }
+    }
 }
 
 /// Routing branch.
@@ -35,12 +44,7 @@ pub struct Or<L, R> {
 
 impl<L, R> Or<L, R> {
     #[inline]
-    pub(crate) fn new<T, U, E>(left: L, right: R) -> Or<L, R>
-    where
-        L: Route<T, Response = U, Error = E>,
-        R: Route<T, Response = U, Error = E>,
-        E: From<Error>,
-    {
+    pub(crate) const fn new(left: L, right: R) -> Or<L, R> {
         Or { left, right }
     }
 }
@@ -57,11 +61,28 @@ where
 
     type Future = Maybe<Either<L::Future, R::Future>, Result<Self::Response, Self::Error>>;
 
+    /// Both branches must be ready, since the branch is only known when the
+    /// request is called.
     fn poll_ready(
         &mut self,
-        _: &mut std::task::Context<'_>,
+        cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Result<(), Self::Error>> {
-        std::task::Poll::Ready(Ok(()))
+        use std::task::Poll;
+
+        let left = match self.left.poll_ready(cx) {
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Ready(Ok(())) => true,
+            Poll::Pending => false,
+        };
+        let right = match self.right.poll_ready(cx) {
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Ready(Ok(())) => true,
+            Poll::Pending => false,
+        };
+        match left && right {
+            true => Poll::Ready(Ok(())),
+            false => Poll::Pending,
+        }
     }
 
     fn call(&mut self, req: T) -> Self::Future {
@@ -91,16 +112,20 @@ where
         }
     }
 
+    /// Both branches are matched. When both match, the one with the higher
+    /// [`Priority`] wins, or the left one when they are equal.
     fn param(&self, req: &T) -> Result<Self::Param, Error> {
-        let err1 = match self.left.param(req) {
-            Err(err) => err,
-            Ok(param) => return Ok(Param::Left(param)),
-        };
-        let err2 = match self.right.param(req) {
-            Err(err) => err,
-            Ok(param) => return Ok(Param::Right(param)),
-        };
-        Err(std::cmp::min(err1, err2))
+        use param::Param as _;
+
+        match (self.left.param(req), self.right.param(req)) {
+            (Ok(left), Ok(right)) => match right.priority().outranks(left.priority()) {
+                true => Ok(Param::Right(right)),
+                false => Ok(Param::Left(left)),
+            },
+            (Ok(left), Err(_)) => Ok(Param::Left(left)),
+            (Err(_), Ok(right)) => Ok(Param::Right(right)),
+            (Err(err1), Err(err2)) => Err(std::cmp::min(err1, err2)),
+        }
     }
 }
 
@@ -113,6 +138,7 @@ mod tests {
     use crate::func::Func;
     use crate::or::Or;
     use crate::param::Param;
+    use crate::priority::Priority;
 
     #[test]
     fn test() {
@@ -155,4 +181,58 @@ mod tests {
         let res = run(or, "/right/2");
         assert!(matches!(res, Ok(2)));
     }
+
+    #[test]
+    fn priority() {
+        struct Any;
+        struct Me;
+        struct User;
+
+        impl Param<&'static str> for User {
+            fn from_request(req: &&'static str) -> Result<Self, Error> {
+                req.strip_prefix("/user/").map(|_| User).ok_or(Error::Path)
+            }
+        }
+
+        impl Param<&'static str> for Any {
+            fn from_request(req: &&'static str) -> Result<Self, Error> {
+                req.strip_prefix("/user/").map(|_| Any).ok_or(Error::Path)
+            }
+
+            fn priority(&self) -> Priority {
+                Priority::new().literal().param()
+            }
+        }
+
+        impl Param<&'static str> for Me {
+            fn from_request(req: &&'static str) -> Result<Self, Error> {
+                match *req == "/user/me" {
+                    true => Ok(Me),
+                    false => Err(Error::Path),
+                }
+            }
+
+            fn priority(&self) -> Priority {
+                Priority::new().literal().literal()
+            }
+        }
+
+        let any = Func::new(|_: &str, _: Any| ready(Ok::<_, Error>("any")));
+        let me = Func::new(|_: &str, _: Me| ready(Ok::<_, Error>("me")));
+
+        // the more specific route wins, regardless of the order.
+        assert!(matches!(run(Or::new(any, me), "/user/me"), Ok("me")));
+        assert!(matches!(run(Or::new(me, any), "/user/me"), Ok("me")));
+        assert!(matches!(run(Or::new(any, me), "/user/1"), Ok("any")));
+
+        // equal priority goes left.
+        assert!(matches!(run(Or::new(any, any), "/user/1"), Ok("any")));
+        let left = Func::new(|_: &str, _: Any| ready(Ok::<_, Error>("left")));
+        assert!(matches!(run(Or::new(left, any), "/user/1"), Ok("left")));
+
+        // the default priority keeps the order.
+        let user = Func::new(|_: &str, _: User| ready(Ok::<_, Error>("user")));
+        assert!(matches!(run(Or::new(user, me), "/user/me"), Ok("user")));
+        assert!(matches!(run(Or::new(me, user), "/user/me"), Ok("me")));
+    }
 }
@@ -1,4 +1,5 @@
 use crate::error::Error;
+use crate::priority::Priority;
 
 /// A param describes a [route][1]'s dependency ([route][1]'s second argument).
 ///
@@ -28,4 +29,15 @@ pub trait Param<T>: Sized {
     /// [3]: crate::error::Error::Prefix
     /// [4]: crate::router::Router::mount
     fn from_request(req: &T) -> Result<Self, Error>;
+
+    /// The priority of this param among the params that match a request.
+    ///
+    /// Returns [`Priority::new`] by default, which is neutral: a route with
+    /// this param keeps the order it is added in, like before priorities
+    /// existed, instead of losing to any derived route that matches the same
+    /// request. See [`Priority`].
+    #[inline]
+    fn priority(&self) -> Priority {
+        Priority::new()
+    }
 }
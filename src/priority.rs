@@ -0,0 +1,162 @@
+/// The priority of a matched [`Param`][1].
+///
+/// When more than one route matches a request, the route whose param has the
+/// highest priority is called. Routes with the same priority are tried in the
+/// order they are added.
+///
+/// [`Priority::new`], which hand-written params and [`Router::mount`][3]
+/// return by default, is neutral: it neither outranks nor is outranked by
+/// another priority, so those routes keep the order they are added in, even
+/// next to derived routes. See [`Priority::outranks`].
+///
+/// A priority has a level, which is `0` unless given explicitly, and the
+/// specificity of the path. Levels are compared first. The specificity
+/// compares the path segment by segment, from the first one: a literal is
+/// more specific than a constrained param, which is more specific than a
+/// param, which is more specific than a wildcard. The end of a path without
+/// a wildcard is more specific than anything, so `"files"` wins over
+/// `"files" / rest*` on `/files`.
+///
+/// `#[derive(Param)]` and `#[route]` compute the priority from the spec, and
+/// the level can be given with `priority = N`:
+///
+/// ```ignore
+/// #[route(GET: "user" / id)]      // "user" / param
+/// #[route(GET: "user" / "me")]    // "user" / literal, wins over the above
+/// #[route(GET: path*, priority = 1)]
+/// ```
+///
+/// A param is constrained when its type is not `String` or [`Capture`][2],
+/// since parsing the segment may fail.
+///
+/// [1]: crate::param::Param
+/// [2]: crate::segment::Capture
+/// [3]: crate::router::Router::mount
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Priority {
+    level: i32,
+    specificity: u64,
+    depth: u8,
+}
+
+/// Bits of each segment in the specificity.
+const BITS: u8 = 3;
+
+/// Maximum number of segments that count in the specificity.
+const MAX_DEPTH: u8 = (u64::BITS / BITS as u32) as u8;
+
+impl Priority {
+    /// The lowest priority at level `0`, which is the priority of a param
+    /// that does not know its path.
+    #[inline]
+    pub const fn new() -> Priority {
+        Priority {
+            level: 0,
+            specificity: 0,
+            depth: 0,
+        }
+    }
+
+    /// Wether a param with this priority is called over one with `other`,
+    /// when both match.
+    ///
+    /// This is `self > other`, except that [`Priority::new`] is neutral and
+    /// never outranks nor is outranked, so the param added first is called.
+    #[inline]
+    pub fn outranks(self, other: Priority) -> bool {
+        self != Priority::new() && other != Priority::new() && self > other
+    }
+
+    /// Set the level.
+    #[inline]
+    pub const fn with_level(self, level: i32) -> Priority {
+        Priority { level, ..self }
+    }
+
+    /// Get the level.
+    #[inline]
+    pub const fn level(&self) -> i32 {
+        self.level
+    }
+
+    /// Add a literal segment.
+    #[inline]
+    pub const fn literal(self) -> Priority {
+        self.segment(4)
+    }
+
+    /// Add a param segment whose parsing may fail, like an integer.
+    #[inline]
+    pub const fn constrained(self) -> Priority {
+        self.segment(3)
+    }
+
+    /// Add a param segment that matches any segment.
+    #[inline]
+    pub const fn param(self) -> Priority {
+        self.segment(2)
+    }
+
+    /// Add a wildcard segment.
+    #[inline]
+    pub const fn wildcard(self) -> Priority {
+        self.segment(1)
+    }
+
+    /// Mark the end of a path that does not end with a wildcard.
+    #[inline]
+    pub const fn end(self) -> Priority {
+        self.segment(5)
+    }
+
+    const fn segment(self, code: u64) -> Priority {
+        if self.depth >= MAX_DEPTH {
+            return self;
+        }
+        let depth = self.depth + 1;
+        let shift = (MAX_DEPTH - depth) * BITS;
+        Priority {
+            level: self.level,
+            specificity: self.specificity | (code << shift),
+            depth,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Priority;
+
+    #[test]
+    fn test() {
+        let me = Priority::new().literal().literal();
+        let id = Priority::new().literal().constrained();
+        let name = Priority::new().literal().param();
+        let rest = Priority::new().literal().wildcard();
+        let exact = Priority::new().literal().end();
+        assert!(me > id);
+        assert!(id > name);
+        assert!(name > rest);
+        assert!(rest > Priority::new());
+        assert!(exact > rest);
+        assert!(exact > name);
+
+        // the first segments matter the most.
+        let deep = Priority::new().param().literal().literal();
+        assert!(name > deep);
+
+        let level = Priority::new().wildcard().with_level(1);
+        assert!(level > me);
+        assert_eq!(level.level(), 1);
+        assert!(Priority::new().with_level(-1) < Priority::new());
+
+        let long = (0..30).fold(Priority::new(), |priority, _| priority.literal());
+        assert!(long > Priority::new());
+
+        // the default is neutral.
+        assert!(me.outranks(id));
+        assert!(!id.outranks(me));
+        assert!(!me.outranks(Priority::new()));
+        assert!(!Priority::new().outranks(rest));
+    }
+}
@@ -0,0 +1,54 @@
+use http::Request;
+use serde::de::DeserializeOwned;
+
+use crate::extract::FromRequestParts;
+use crate::extract::Rejection;
+
+/// Request query.
+///
+/// The query string is deserialized with [`serde_urlencoded`][1]. A missing
+/// query string is treated as an empty one.
+///
+/// [1]: https://docs.rs/serde_urlencoded/0.7
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Query<Q>(pub Q);
+
+impl<B, Q> FromRequestParts<Request<B>> for Query<Q>
+where
+    Q: DeserializeOwned,
+{
+    type Rejection = Rejection;
+
+    fn from_request_parts(req: &Request<B>) -> Result<Self, Self::Rejection> {
+        serde_urlencoded::from_str(req.uri().query().unwrap_or(""))
+            .map(Query)
+            .map_err(|err| Rejection::InvalidQuery(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use http::Request;
+
+    use super::Query;
+    use crate::extract::FromRequestParts;
+    use crate::extract::Rejection;
+
+    #[test]
+    fn from_request_parts() {
+        let req = Request::builder().uri("/?a=1&b=2").body(()).unwrap();
+        let res = Query::<BTreeMap<String, i32>>::from_request_parts(&req);
+        let map = BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)]);
+        assert_eq!(res, Ok(Query(map)));
+
+        let req = Request::builder().uri("/").body(()).unwrap();
+        let res = Query::<BTreeMap<String, i32>>::from_request_parts(&req);
+        assert_eq!(res, Ok(Query(BTreeMap::new())));
+
+        let req = Request::builder().uri("/?a=x").body(()).unwrap();
+        let res = Query::<BTreeMap<String, i32>>::from_request_parts(&req);
+        assert!(matches!(res, Err(Rejection::InvalidQuery(..))));
+    }
+}
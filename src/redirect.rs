@@ -0,0 +1,461 @@
+use std::future::Ready;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::sync::Arc;
+
+use http::header::HeaderValue;
+use http::header::LOCATION;
+use http::Response;
+use http::StatusCode;
+use tower_service::Service;
+
+use crate::error::Error;
+use crate::param;
+use crate::priority::Priority;
+use crate::request::PathReq;
+use crate::route::Route;
+
+/// Redirect a path to another path.
+///
+/// The pattern is matched segment by segment like [`#[route]`][1]: a
+/// segment is either a literal, a param written as `{name}`, or a wildcard
+/// written as `{name*}`, which must be the last one. The captured params are
+/// substituted into the target, and the query string of the request is
+/// appended to it.
+///
+/// A redirect matches every method, since it only looks at the path. A
+/// capture that starts with `/` or `\` does not match, so a request for
+/// `/docs//evil.example` cannot redirect to another host through a target
+/// like `/{path}`.
+///
+/// ```ignore
+/// let router = Router::new(user)
+///     .redirect("/old/{id}", "/new/{id}", StatusCode::PERMANENT_REDIRECT)
+///     .redirect("/docs/{path*}", "/manual/{path}", StatusCode::FOUND);
+/// ```
+///
+/// A redirect has a [`Priority`] computed from its pattern, with params
+/// counted as unconstrained, so it competes with other routes like any
+/// other route.
+///
+/// Note that application code cannot construct this struct directly. This is
+/// exported for type annotation only.
+///
+/// [1]: macro@crate::route
+pub struct Redirect<B, E> {
+    inner: Arc<Inner>,
+    _marker: PhantomData<fn() -> (B, E)>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    from: Vec<Piece>,
+    to: Vec<Part>,
+    status: StatusCode,
+    priority: Priority,
+}
+
+#[derive(Debug)]
+enum Piece {
+    Lit(String),
+    Param(String),
+    Wild(String),
+}
+
+impl Piece {
+    fn name(&self) -> Option<&str> {
+        match self {
+            Piece::Lit(..) => None,
+            Piece::Param(name) | Piece::Wild(name) => Some(name),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Part {
+    Text(String),
+    Capture(usize),
+}
+
+impl<B, E> Redirect<B, E> {
+    /// Parse `from` and `to`.
+    ///
+    /// Panics when a pattern is invalid or `status` is not a redirection, as
+    /// documented in [`Router::redirect`][1].
+    ///
+    /// [1]: crate::router::Router::redirect
+    pub(crate) fn new(from: &str, to: &str, status: StatusCode) -> Redirect<B, E> {
+        assert!(
+            status.is_redirection(),
+            "redirect status must be 3xx, found {}",
+            status
+        );
+        if HeaderValue::try_from(to).is_err() {
+            panic!("invalid character in redirect target {:?}", to);
+        }
+        let from = parse_from(from);
+        let to = parse_to(to, &from);
+        let priority = from
+            .iter()
+            .fold(Priority::new(), |priority, piece| match piece {
+                Piece::Lit(..) => priority.literal(),
+                Piece::Param(..) => priority.param(),
+                Piece::Wild(..) => priority.wildcard(),
+            });
+        let priority = match from.last() {
+            Some(Piece::Wild(..)) => priority,
+            _ => priority.end(),
+        };
+        Redirect {
+            inner: Arc::new(Inner {
+                from,
+                to,
+                status,
+                priority,
+            }),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Match `path` against the pattern, returning the range of every capture
+    /// in order.
+    fn captures(&self, path: &str) -> Result<Vec<Range<usize>>, Error> {
+        let mut path = path.strip_prefix('/').ok_or(Error::Path)?;
+        let mut offset = 1;
+        let mut captures = Vec::new();
+        for piece in self.inner.from.iter() {
+            let next = match piece {
+                Piece::Wild(..) => path,
+                Piece::Lit(..) | Piece::Param(..) => {
+                    path.split_once('/').map_or(path, |(next, _)| next)
+                }
+            };
+            match piece {
+                Piece::Lit(lit) if next != lit => return Err(Error::Path),
+                Piece::Lit(..) => {}
+                // Browsers read a leading `//` or `/\` in the location as
+                // another host.
+                Piece::Param(..) | Piece::Wild(..) if next.starts_with(['/', '\\']) => {
+                    return Err(Error::Path)
+                }
+                Piece::Param(..) | Piece::Wild(..) => captures.push(offset..offset + next.len()),
+            }
+            let consumed = match path.len() > next.len() {
+                true => next.len() + 1,
+                false => next.len(),
+            };
+            path = &path[consumed..];
+            offset += consumed;
+        }
+        match path.is_empty() {
+            true => Ok(captures),
+            false => Err(Error::Path),
+        }
+    }
+
+    /// Build the location from the captures of `path`.
+    fn location(&self, path: &str, query: Option<&str>, captures: &[Range<usize>]) -> String {
+        let mut location = String::new();
+        for part in self.inner.to.iter() {
+            match part {
+                Part::Text(text) => location.push_str(text),
+                Part::Capture(index) => location.push_str(&path[captures[*index].clone()]),
+            }
+        }
+        if let Some(query) = query.filter(|query| !query.is_empty()) {
+            location.push(match location.contains('?') {
+                true => '&',
+                false => '?',
+            });
+            location.push_str(query);
+        }
+        location
+    }
+}
+
+/// Parse the pattern to match.
+fn parse_from(pattern: &str) -> Vec<Piece> {
+    let path = match pattern.strip_prefix('/') {
+        Some(path) => path,
+        None => panic!("redirect pattern must start with '/', found {:?}", pattern),
+    };
+    if path.is_empty() {
+        return Vec::new();
+    }
+
+    let mut pieces: Vec<Piece> = Vec::new();
+    for segment in path.split('/') {
+        if matches!(pieces.last(), Some(Piece::Wild(..))) {
+            panic!("wildcard must be last in redirect pattern {:?}", pattern);
+        }
+        let piece = match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(name) => match name.strip_suffix('*') {
+                Some(name) => Piece::Wild(name.to_string()),
+                None => Piece::Param(name.to_string()),
+            },
+            None => Piece::Lit(segment.to_string()),
+        };
+        match &piece {
+            Piece::Lit(lit) if lit.is_empty() => {
+                panic!("empty segment in redirect pattern {:?}", pattern)
+            }
+            Piece::Lit(lit) if lit.contains(['{', '}']) => {
+                panic!(
+                    "invalid segment {:?} in redirect pattern {:?}",
+                    lit, pattern
+                )
+            }
+            _ => {}
+        }
+        if let Some(name) = piece.name() {
+            if !is_name(name) {
+                panic!("invalid param {:?} in redirect pattern {:?}", name, pattern);
+            }
+            if pieces.iter().any(|piece| piece.name() == Some(name)) {
+                panic!(
+                    "duplicate param {:?} in redirect pattern {:?}",
+                    name, pattern
+                );
+            }
+        }
+        pieces.push(piece);
+    }
+    pieces
+}
+
+/// Parse the target, resolving every `{name}` to a capture of `from`.
+fn parse_to(target: &str, from: &[Piece]) -> Vec<Part> {
+    let mut parts = Vec::new();
+    let mut rest = target;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => panic!("unclosed '{{' in redirect target {:?}", target),
+        };
+        if rest[..start].contains('}') {
+            panic!("unopened '}}' in redirect target {:?}", target);
+        }
+        if start > 0 {
+            parts.push(Part::Text(rest[..start].to_string()));
+        }
+        let name = &rest[start + 1..end];
+        let name = name.strip_suffix('*').unwrap_or(name);
+        let index = from
+            .iter()
+            .filter_map(Piece::name)
+            .position(|param| param == name);
+        match index {
+            Some(index) => parts.push(Part::Capture(index)),
+            None => panic!("unknown param {:?} in redirect target {:?}", name, target),
+        }
+        rest = &rest[end + 1..];
+    }
+    if rest.contains('}') {
+        panic!("unopened '}}' in redirect target {:?}", target);
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Text(rest.to_string()));
+    }
+    parts
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c == '_' || c.is_alphanumeric())
+}
+
+impl<B, E> Clone for Redirect<B, E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Redirect {
+            inner: self.inner.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<B, E> std::fmt::Debug for Redirect<B, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Redirect")
+            .field("from", &self.inner.from)
+            .field("to", &self.inner.to)
+            .field("status", &self.inner.status)
+            .finish()
+    }
+}
+
+impl<B, E, T> Service<T> for Redirect<B, E>
+where
+    T: PathReq,
+    B: Default,
+    E: From<Error>,
+{
+    type Response = Response<B>;
+
+    type Error = E;
+
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    #[inline]
+    fn poll_ready(
+        &mut self,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: T) -> Self::Future {
+        match self.param(&req) {
+            Ok(param) => self.call_with_param(req, param),
+            Err(err) => std::future::ready(Err(err.into())),
+        }
+    }
+}
+
+impl<B, E, T> Route<T> for Redirect<B, E>
+where
+    T: PathReq,
+    B: Default,
+    E: From<Error>,
+{
+    type Param = Param;
+
+    fn call_with_param(&mut self, req: T, param: Self::Param) -> Self::Future {
+        let location = self.location(req.path(), req.query(), &param.captures);
+        let location = match HeaderValue::try_from(location) {
+            Ok(location) => location,
+            Err(_) => return std::future::ready(Err(Error::Path.into())),
+        };
+        let mut res = Response::new(B::default());
+        *res.status_mut() = self.inner.status;
+        res.headers_mut().insert(LOCATION, location);
+        std::future::ready(Ok(res))
+    }
+
+    fn param(&self, req: &T) -> Result<Self::Param, Error> {
+        Ok(Param {
+            captures: self.captures(req.path())?,
+            priority: self.inner.priority,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Param {
+    captures: Vec<Range<usize>>,
+    priority: Priority,
+}
+
+impl<T> param::Param<T> for Param {
+    #[inline]
+    fn from_request(_: &T) -> Result<Self, Error> {
+        panic!("BUG: redirect should call param from Route trait.");
+    }
+
+    #[inline]
+    fn priority(&self) -> Priority {
+        self.priority
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::header::LOCATION;
+    use http::Request;
+    use http::Response;
+    use http::StatusCode;
+
+    use super::Redirect;
+    use crate::error::Error;
+    use crate::exec::run;
+    use crate::macros::param;
+    use crate::router::Router;
+
+    param!(New, GET, "/new/me");
+
+    async fn new(_: Request<()>, _: New) -> Result<Response<()>, Error> {
+        Ok(Response::new(()))
+    }
+
+    fn req(uri: &'static str) -> Request<()> {
+        Request::builder().uri(uri).body(()).unwrap()
+    }
+
+    fn location(res: &Response<()>) -> &str {
+        res.headers()[LOCATION].to_str().unwrap()
+    }
+
+    #[test]
+    fn test() {
+        let router = Router::new(new)
+            .redirect("/old/{id}", "/new/{id}", StatusCode::PERMANENT_REDIRECT)
+            .redirect("/docs/{path*}", "/manual/{path}?v=2", StatusCode::FOUND);
+
+        let res = run(router.clone(), req("/old/7")).unwrap();
+        assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(location(&res), "/new/7");
+
+        let res = run(router.clone(), req("/old/7?a=1&b=2")).unwrap();
+        assert_eq!(location(&res), "/new/7?a=1&b=2");
+
+        let res = run(router.clone(), req("/docs/a/b?c")).unwrap();
+        assert_eq!(res.status(), StatusCode::FOUND);
+        assert_eq!(location(&res), "/manual/a/b?v=2&c");
+
+        let res = run(router.clone(), req("/new/me")).unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = run(router.clone(), req("/old/7/8"));
+        assert_eq!(res.unwrap_err(), Error::Path);
+
+        let res = run(router, req("/other"));
+        assert_eq!(res.unwrap_err(), Error::Path);
+    }
+
+    #[test]
+    fn open_redirect() {
+        let router = Router::new(new)
+            .redirect("/go/{path*}", "/{path}", StatusCode::FOUND)
+            .redirect("/to/{host}", "/{host}", StatusCode::FOUND);
+
+        let res = run(router.clone(), req("/go//evil.example"));
+        assert_eq!(res.unwrap_err(), Error::Path);
+
+        let res = run(router.clone(), req("/to/%5Cevil.example"));
+        assert_eq!(location(&res.unwrap()), "/%5Cevil.example");
+
+        let res = run(router, req("/go/a//b")).unwrap();
+        assert_eq!(location(&res), "/a//b");
+
+        let redirect = Redirect::<(), Error>::new("/to/{host}", "/{host}", StatusCode::FOUND);
+        let res = run(redirect, "/to/\\evil.example");
+        assert_eq!(res.unwrap_err(), Error::Path);
+    }
+
+    #[test]
+    fn root() {
+        let redirect = Redirect::<(), Error>::new("/", "/home", StatusCode::FOUND);
+        let res = run(redirect.clone(), req("/?q")).unwrap();
+        assert_eq!(location(&res), "/home?q");
+
+        let res = run(redirect, "/a");
+        assert_eq!(res.unwrap_err(), Error::Path);
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown param")]
+    fn unknown_param() {
+        Redirect::<(), Error>::new("/old/{id}", "/new/{name}", StatusCode::FOUND);
+    }
+
+    #[test]
+    #[should_panic(expected = "wildcard must be last")]
+    fn wildcard_last() {
+        Redirect::<(), Error>::new("/{path*}/edit", "/", StatusCode::FOUND);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be 3xx")]
+    fn status() {
+        Redirect::<(), Error>::new("/", "/", StatusCode::OK);
+    }
+}
@@ -14,6 +14,29 @@ use crate::error::Error;
 pub trait PathReq {
     /// The associated path.
     fn path(&self) -> &str;
+
+    /// The associated path and query, shared with the request.
+    ///
+    /// The path of the returned value must be the same as [`PathReq::path`].
+    /// It lets [`Capture`][1] keep a part of the path without copying it.
+    /// Returns `None` by default.
+    ///
+    /// [1]: crate::segment::Capture
+    #[inline]
+    fn shared_path(&self) -> Option<PathAndQuery> {
+        None
+    }
+
+    /// The associated query string, without the leading `?`.
+    ///
+    /// It lets [`Redirect`][1] preserve the query string. Returns `None` by
+    /// default.
+    ///
+    /// [1]: crate::redirect::Redirect
+    #[inline]
+    fn query(&self) -> Option<&str> {
+        None
+    }
 }
 
 impl PathReq for &str {
@@ -35,6 +58,16 @@ impl PathReq for PathAndQuery {
     fn path(&self) -> &str {
         self.as_str()
     }
+
+    #[inline]
+    fn shared_path(&self) -> Option<PathAndQuery> {
+        Some(self.clone())
+    }
+
+    #[inline]
+    fn query(&self) -> Option<&str> {
+        PathAndQuery::query(self)
+    }
 }
 
 impl PathReq for Uri {
@@ -42,6 +75,16 @@ impl PathReq for Uri {
     fn path(&self) -> &str {
         Uri::path(self)
     }
+
+    #[inline]
+    fn shared_path(&self) -> Option<PathAndQuery> {
+        self.path_and_query().cloned()
+    }
+
+    #[inline]
+    fn query(&self) -> Option<&str> {
+        Uri::query(self)
+    }
 }
 
 impl<T> PathReq for Request<T> {
@@ -49,6 +92,16 @@ impl<T> PathReq for Request<T> {
     fn path(&self) -> &str {
         self.uri().path()
     }
+
+    #[inline]
+    fn shared_path(&self) -> Option<PathAndQuery> {
+        self.uri().shared_path()
+    }
+
+    #[inline]
+    fn query(&self) -> Option<&str> {
+        self.uri().query()
+    }
 }
 
 /// A request that has an HTTP method.
@@ -65,11 +118,43 @@ pub trait MethodReq {
 
 impl<T> MethodReq for Request<T> {
     #[inline]
+    #[allow(unused_qualifications)]
     fn method(&self) -> &http::Method {
         http::Request::method(self)
     }
 }
 
+/// A request that has extensions.
+///
+/// This trait is required by [`Router::with_state`][1] and the
+/// [`State`][2] and [`Extension`][3] extractors.
+///
+/// [1]: crate::router::Router::with_state
+/// [2]: crate::state::State
+/// [3]: crate::extract::Extension
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+pub trait ExtensionReq {
+    /// The associated extensions.
+    fn extensions(&self) -> &http::Extensions;
+
+    /// The associated extensions, mutably.
+    fn extensions_mut(&mut self) -> &mut http::Extensions;
+}
+
+#[cfg(feature = "response")]
+impl<T> ExtensionReq for Request<T> {
+    #[inline]
+    fn extensions(&self) -> &http::Extensions {
+        Request::extensions(self)
+    }
+
+    #[inline]
+    fn extensions_mut(&mut self) -> &mut http::Extensions {
+        Request::extensions_mut(self)
+    }
+}
+
 /// A request that can remove it's prefix.
 ///
 /// This trait is required by [`Mount`][1].
@@ -0,0 +1,255 @@
+use std::convert::Infallible;
+use std::future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use http::header;
+use http::HeaderMap;
+use http::HeaderValue;
+use http::Response;
+use http::StatusCode;
+use tower_service::Service;
+
+use crate::error::Error;
+use crate::future::Oneshot;
+
+/// Conversion into a response.
+///
+/// This is what a handler registered with [`Router::handle`][1] returns. `B`
+/// is the response body.
+///
+/// [1]: crate::router::Router::handle
+pub trait IntoResponse<B> {
+    /// Construct the response.
+    fn into_response(self) -> Response<B>;
+}
+
+impl<B> IntoResponse<B> for Response<B> {
+    #[inline]
+    fn into_response(self) -> Response<B> {
+        self
+    }
+}
+
+impl<B: Default> IntoResponse<B> for StatusCode {
+    #[inline]
+    fn into_response(self) -> Response<B> {
+        let mut res = Response::new(B::default());
+        *res.status_mut() = self;
+        res
+    }
+}
+
+impl<B: From<&'static str>> IntoResponse<B> for &'static str {
+    #[inline]
+    fn into_response(self) -> Response<B> {
+        text(B::from(self))
+    }
+}
+
+impl<B: From<String>> IntoResponse<B> for String {
+    #[inline]
+    fn into_response(self) -> Response<B> {
+        text(B::from(self))
+    }
+}
+
+impl<B, T: IntoResponse<B>> IntoResponse<B> for (StatusCode, T) {
+    #[inline]
+    fn into_response(self) -> Response<B> {
+        let mut res = self.1.into_response();
+        *res.status_mut() = self.0;
+        res
+    }
+}
+
+impl<B, T: IntoResponse<B>> IntoResponse<B> for (HeaderMap, T) {
+    #[inline]
+    fn into_response(self) -> Response<B> {
+        let mut res = self.1.into_response();
+        res.headers_mut().extend(self.0);
+        res
+    }
+}
+
+impl<B, T, E> IntoResponse<B> for Result<T, E>
+where
+    T: IntoResponse<B>,
+    E: IntoResponse<B>,
+{
+    #[inline]
+    fn into_response(self) -> Response<B> {
+        match self {
+            Ok(ok) => ok.into_response(),
+            Err(err) => err.into_response(),
+        }
+    }
+}
+
+impl<B: From<String>> IntoResponse<B> for Error {
+    #[inline]
+    fn into_response(self) -> Response<B> {
+        Error::into_response(self)
+    }
+}
+
+impl<B> IntoResponse<B> for Infallible {
+    fn into_response(self) -> Response<B> {
+        match self {}
+    }
+}
+
+fn text<B>(body: B) -> Response<B> {
+    let mut res = Response::new(body);
+    res.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/plain; charset=utf-8"),
+    );
+    res
+}
+
+/// Turn errors into responses.
+///
+/// Any error returned by the inner service is converted into a response, so
+/// this service never fails. This is the service to hand to a server like
+/// [`hyper`][1] once all routes are registered.
+///
+/// This service is always ready. The inner service is cloned for every
+/// request, and a readiness error of the clone is converted like any other
+/// error.
+///
+/// Note that application code cannot construct this struct directly. This is
+/// exported for type annotation only.
+///
+/// [1]: https://docs.rs/hyper/0.14
+#[derive(Clone, Copy, Debug)]
+pub struct Respond<S> {
+    inner: S,
+}
+
+impl<S> Respond<S> {
+    #[inline]
+    pub(crate) fn new<T, B>(inner: S) -> Respond<S>
+    where
+        S: Clone + Service<T, Response = Response<B>>,
+        S::Error: IntoResponse<B>,
+    {
+        Respond { inner }
+    }
+}
+
+impl<S, T, B> Service<T> for Respond<S>
+where
+    S: Clone + Service<T, Response = Response<B>>,
+    S::Error: IntoResponse<B>,
+{
+    type Response = Response<B>;
+
+    type Error = Infallible;
+
+    type Future = Future<Oneshot<S, T>>;
+
+    #[inline]
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn call(&mut self, req: T) -> Self::Future {
+        Future {
+            fut: Oneshot::new(self.inner.clone(), req),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[pin_project::pin_project]
+pub struct Future<F> {
+    #[pin]
+    fut: F,
+}
+
+impl<F, B, E> future::Future for Future<F>
+where
+    F: future::Future<Output = Result<Response<B>, E>>,
+    E: IntoResponse<B>,
+{
+    type Output = Result<Response<B>, Infallible>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().fut.poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Ok(res)) => Poll::Ready(Ok(res)),
+            Poll::Ready(Err(err)) => Poll::Ready(Ok(err.into_response())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+    use http::HeaderValue;
+    use http::Request;
+    use http::Response;
+    use http::StatusCode;
+
+    use super::IntoResponse;
+
+    use crate::error::Error;
+    use crate::exec::run;
+    use crate::macros::param;
+    use crate::router::Router;
+
+    param!(Home, GET, "/");
+
+    async fn home(_: Request<()>, _: Home) -> Result<Response<String>, Error> {
+        Ok(Response::new("home".to_string()))
+    }
+
+    fn req(method: http::Method, path: &'static str) -> Request<()> {
+        Request::builder()
+            .method(method)
+            .uri(http::Uri::from_static(path))
+            .body(())
+            .unwrap()
+    }
+
+    #[test]
+    fn into_response() {
+        let res: Response<String> = "text".into_response();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()["content-type"], "text/plain; charset=utf-8");
+        assert_eq!(res.body(), "text");
+
+        let res: Response<String> = (StatusCode::CREATED, "text".to_string()).into_response();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert_eq!(res.body(), "text");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-custom", HeaderValue::from_static("value"));
+        let res: Response<String> = (headers, StatusCode::ACCEPTED).into_response();
+        assert_eq!(res.status(), StatusCode::ACCEPTED);
+        assert_eq!(res.headers()["x-custom"], "value");
+        assert_eq!(res.body(), "");
+
+        let res: Response<String> = Err::<&str, _>(Error::Method).into_response();
+        assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[test]
+    fn test() {
+        let router = Router::new(home).respond();
+
+        let res = run(router, req(http::Method::GET, "/")).unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.body(), "home");
+
+        let res = run(router, req(http::Method::GET, "/nowhere")).unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let res = run(router, req(http::Method::POST, "/")).unwrap();
+        assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+}
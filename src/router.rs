@@ -1,21 +1,48 @@
 use std::future::Future;
 
+use http::Response;
+use http::StatusCode;
+use tower_layer::Layer;
 use tower_service::Service;
 
+use crate::around::Around;
+use crate::around::Next;
+use crate::boxed::BoxRoute;
+use crate::boxed::BoxRouter;
+use crate::boxed::UnsyncBoxRoute;
+use crate::boxed::UnsyncBoxRouter;
 use crate::error::Error;
 use crate::func::Func;
+#[cfg(feature = "response")]
+use crate::handler::Handler;
+use crate::layer::Layered;
+use crate::map::MapErr;
+use crate::map::MapResponse;
 use crate::mount::Mount;
 use crate::or::Or;
 use crate::param::Param;
+use crate::redirect::Redirect;
 use crate::request::PathReq;
 use crate::request::RemovePrefix;
+#[cfg(feature = "response")]
+use crate::response::IntoResponse;
+#[cfg(feature = "response")]
+use crate::response::Respond;
 use crate::route::Route;
+#[cfg(feature = "response")]
+use crate::state::WithState;
 use crate::void::Void;
 use crate::with::With;
+use crate::with::WithService;
 
 /// The router instance.
 ///
 /// Note that a router does not implement Route. It only implement service.
+///
+/// A request that no route matches fails with a [`tackt::Error`][crate::Error]
+/// by default, so the router can still be combined with other routes. Turning
+/// routing errors into `404` and `405` responses is opt-in with
+/// [`Router::respond`], or with `From<tackt::Error>` for `http::Response`.
 #[derive(Clone, Copy, Debug)]
 pub struct Router<R> {
     inner: R,
@@ -31,11 +58,12 @@ where
 
     type Future = R::Future;
 
+    #[inline]
     fn poll_ready(
         &mut self,
-        _: &mut std::task::Context<'_>,
+        cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Result<(), Self::Error>> {
-        std::task::Poll::Ready(Ok(()))
+        self.inner.poll_ready(cx)
     }
 
     fn call(&mut self, req: T) -> Self::Future {
@@ -43,6 +71,18 @@ where
     }
 }
 
+impl<R> Router<R> {
+    #[inline]
+    pub(crate) const fn from_inner(inner: R) -> Router<R> {
+        Router { inner }
+    }
+
+    #[inline]
+    pub(crate) fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
 impl<T, U> Router<Void<T, U>> {
     /// Create a router that does not match any request.
     pub const fn void() -> Router<Void<T, U>> {
@@ -66,6 +106,24 @@ impl<F, P> Router<Func<F, P>> {
     }
 }
 
+#[cfg(feature = "response")]
+#[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+impl<F, A, B> Router<Handler<F, A, B>> {
+    /// Create a new router with the handler.
+    ///
+    /// Unlike [`Router::new`], the handler may take extracted arguments and
+    /// return anything that implements [`IntoResponse`]. See [`Handler`].
+    ///
+    /// The request type is inferred from where the router is used, so a
+    /// handler that does not take the request itself still works.
+    #[inline]
+    pub fn handler(handler: F) -> Router<Handler<F, A, B>> {
+        Router {
+            inner: Handler::new(handler),
+        }
+    }
+}
+
 impl<R> Router<R> {
     /// Add new route to this router.
     #[inline]
@@ -82,6 +140,103 @@ impl<R> Router<R> {
         }
     }
 
+    /// Add new handler to this router.
+    ///
+    /// Unlike [`Router::route`], the handler may take extracted arguments and
+    /// return anything that implements [`IntoResponse`]. See [`Handler`].
+    /// Routing errors stay as [`tackt::Error`][crate::Error] until
+    /// [`Router::respond`] converts them.
+    #[inline]
+    #[cfg(feature = "response")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+    pub fn handle<F, A, B>(self, handler: F) -> Router<Or<R, Handler<F, A, B>>> {
+        Router {
+            inner: Or::new(self.inner, Handler::new(handler)),
+        }
+    }
+
+    /// Redirect requests matching `from` to `to` with `status`.
+    ///
+    /// `from` uses the same segments as [`#[route]`][1], written as a path:
+    /// `{name}` is a param and `{name*}` is a wildcard. Every param used in
+    /// `to` is replaced with the captured segment, and the query string of
+    /// the request is preserved. The redirect matches every method. See
+    /// [`Redirect`].
+    ///
+    /// ```ignore
+    /// let router = Router::new(user)
+    ///     .redirect("/old/{id}", "/new/{id}", StatusCode::PERMANENT_REDIRECT);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `status` is not a redirection, when `from` does not start
+    /// with `/` or has an invalid segment, or when `to` uses a param that is
+    /// not in `from`, or is not a valid header value.
+    ///
+    /// [1]: macro@crate::route
+    #[inline]
+    pub fn redirect<T, B, E>(
+        self,
+        from: &str,
+        to: &str,
+        status: StatusCode,
+    ) -> Router<Or<R, Redirect<B, E>>>
+    where
+        R: Route<T, Response = Response<B>, Error = E>,
+        T: PathReq,
+        B: Default,
+        E: From<Error>,
+    {
+        Router {
+            inner: Or::new(self.inner, Redirect::new(from, to, status)),
+        }
+    }
+
+    /// Add every route of another router to this router.
+    ///
+    /// Routes of this router are preferred over the routes of `other` with
+    /// the same [`Priority`][crate::priority::Priority].
+    ///
+    /// Together with [`Router::layer`], this applies a middleware to only some
+    /// of the routes:
+    ///
+    /// ```ignore
+    /// let router = Router::new(public).merge(Router::new(private).layer(auth));
+    /// ```
+    #[inline]
+    pub fn merge<O, T, U, E>(self, other: Router<O>) -> Router<Or<R, O>>
+    where
+        R: Route<T, Response = U, Error = E>,
+        O: Route<T, Response = U, Error = E>,
+        E: From<Error>,
+    {
+        Router {
+            inner: Or::new(self.inner, other.inner),
+        }
+    }
+
+    /// Wrap the routes of this router with a [`Layer`].
+    ///
+    /// The layer wraps the handling, not the matching: it only sees requests
+    /// that one of the routes matches, so the router can still be combined
+    /// with other routes with [`Router::merge`] or [`Router::mount`].
+    ///
+    /// The layered service is cloned for every request and is polled for
+    /// readiness before being called.
+    #[inline]
+    pub fn layer<L, T>(self, layer: L) -> Router<Layered<R, L::Service>>
+    where
+        R: Clone + Route<T>,
+        R::Error: From<Error>,
+        L: Layer<R>,
+        L::Service: Clone + Service<T, Response = R::Response, Error = R::Error>,
+    {
+        Router {
+            inner: Layered::new(self.inner, layer),
+        }
+    }
+
     /// Mount a service at prefix.
     ///
     /// Any request to prefix will be delegated to the service with the prefix
@@ -118,10 +273,15 @@ impl<R> Router<R> {
     }
 
     /// Add middleware to the router.
+    ///
+    /// `func` may transform or reject the request. It only runs on requests
+    /// that one of the routes matches, so a request for a missing route is
+    /// not rejected by the middleware, and the router can still be combined
+    /// with other routes with [`Router::merge`] or [`Router::mount`].
     #[inline]
     pub fn with<F, T, Fut>(self, func: F) -> Router<With<R, F>>
     where
-        R: Clone + Service<T>,
+        R: Clone + Route<T>,
         F: FnMut(T) -> Fut,
         Fut: Future<Output = Result<T, R::Error>>,
     {
@@ -129,6 +289,163 @@ impl<R> Router<R> {
             inner: With::new(self.inner, func),
         }
     }
+
+    /// Add middleware to a router that is only a service.
+    ///
+    /// Like [`Router::with`], for a router that does not implement [`Route`].
+    /// Since the router cannot be matched first, `func` runs on every
+    /// request, including unmatched paths, so the middleware may reject a
+    /// request that would otherwise be not found.
+    #[inline]
+    pub fn with_service<F, T, Fut>(self, func: F) -> Router<WithService<R, F>>
+    where
+        R: Clone + Service<T>,
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<T, R::Error>>,
+    {
+        Router {
+            inner: WithService::new(self.inner, func),
+        }
+    }
+
+    /// Map the response of this router.
+    #[inline]
+    pub fn map_response<F, T, U>(self, func: F) -> Router<MapResponse<R, F>>
+    where
+        R: Service<T>,
+        F: Clone + FnMut(R::Response) -> U,
+    {
+        Router {
+            inner: MapResponse::new(self.inner, func),
+        }
+    }
+
+    /// Map the error of this router.
+    ///
+    /// When this router is called as a service, like at the top or behind
+    /// [`Router::mount`], its routing errors also pass through `func`. Once it
+    /// is combined with other routes, like with [`Router::route`] or
+    /// [`Router::merge`], a request that matches none of them fails with a
+    /// routing error converted by `From<tackt::Error>` instead, without
+    /// `func`. So the new error must still implement `From<tackt::Error>` to
+    /// add more routes afterward.
+    #[inline]
+    pub fn map_err<F, T, E>(self, func: F) -> Router<MapErr<R, F>>
+    where
+        R: Service<T>,
+        F: Clone + FnMut(R::Error) -> E,
+    {
+        Router {
+            inner: MapErr::new(self.inner, func),
+        }
+    }
+
+    /// Add middleware that runs around this router.
+    ///
+    /// `func` receives the request and [`Next`], the rest of the router. It
+    /// may answer by itself or call [`Next::run`] and inspect the result:
+    ///
+    /// ```ignore
+    /// async fn timing<S>(req: Request<Body>, next: Next<S>) -> Result<Response<Body>, Error>
+    /// where
+    ///     S: Service<Request<Body>, Response = Response<Body>, Error = Error>,
+    /// {
+    ///     let start = Instant::now();
+    ///     let res = next.run(req).await;
+    ///     log::info!("took {:?}", start.elapsed());
+    ///     res
+    /// }
+    /// ```
+    ///
+    /// Like [`Router::layer`], the middleware only runs on requests that one
+    /// of the routes matches.
+    #[inline]
+    pub fn around<F, T, U, E, Fut>(self, func: F) -> Router<Around<R, F>>
+    where
+        R: Clone + Service<T>,
+        F: FnMut(T, Next<R>) -> Fut,
+        Fut: Future<Output = Result<U, E>>,
+    {
+        Router {
+            inner: Around::new(self.inner, func),
+        }
+    }
+
+    /// Erase the type of this router.
+    ///
+    /// The resulting router can be named as [`BoxRouter`], e.g. to store it
+    /// in a struct field, and can still be combined with other routes. A list
+    /// of boxed routers can be collected into a single one:
+    ///
+    /// ```ignore
+    /// let router: BoxRouter<_, _, _> = plugins.iter().map(|p| p.router()).collect();
+    /// ```
+    ///
+    /// Every request to a boxed router allocates its future.
+    #[inline]
+    pub fn boxed<T, U, E>(self) -> BoxRouter<T, U, E>
+    where
+        R: Clone + Route<T, Response = U, Error = E> + Send + Sync + 'static,
+        R::Param: Send + 'static,
+        R::Future: Send + 'static,
+    {
+        Router {
+            inner: BoxRoute::new(self.inner),
+        }
+    }
+
+    /// Erase the type of this router, that is neither `Send` nor `Sync`.
+    ///
+    /// See [`Router::boxed`].
+    #[inline]
+    pub fn boxed_unsync<T, U, E>(self) -> UnsyncBoxRouter<T, U, E>
+    where
+        R: Clone + Route<T, Response = U, Error = E> + 'static,
+        R::Param: 'static,
+        R::Future: 'static,
+    {
+        Router {
+            inner: UnsyncBoxRoute::new(self.inner),
+        }
+    }
+
+    /// Share a state with every route of this router.
+    ///
+    /// The state is cloned into each request's extensions, where handlers
+    /// can take it with the [`State`][crate::State] extractor. It should be
+    /// cheap to clone, e.g. wrapped in an [`Arc`][std::sync::Arc].
+    ///
+    /// Only the routes added before this call see the state.
+    #[inline]
+    #[cfg(feature = "response")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+    pub fn with_state<S>(self, state: S) -> Router<WithState<R, S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        Router {
+            inner: WithState::new(self.inner, state),
+        }
+    }
+
+    /// Turn every error of this router into a response.
+    ///
+    /// This should be the last step when building a router, since the
+    /// resulting router never fails and thus cannot be combined with other
+    /// routes. A [`tackt::Error`][crate::Error] becomes a `404 Not Found` or
+    /// `405 Method Not Allowed` response.
+    #[inline]
+    #[cfg(feature = "response")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "response")))]
+    pub fn respond<T, B>(self) -> Router<Respond<R>>
+    where
+        R: Clone + Service<T, Response = Response<B>>,
+        R::Error: IntoResponse<B>,
+    {
+        Router {
+            inner: Respond::new(self.inner),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,198 @@
+use std::ops::Deref;
+use std::ops::Range;
+use std::str::FromStr;
+
+use http::uri::PathAndQuery;
+
+use crate::error::Error;
+use crate::request::PathReq;
+
+/// A value parsed from a path segment.
+///
+/// This trait is used by [`#[derive(Param)]`][1] and [`#[route]`][2] to parse
+/// params and wildcards. It is implemented for every type that implements
+/// [`FromStr`], and for [`Capture`], which does not copy the segment.
+///
+/// [1]: crate::param::Param
+/// [2]: macro@crate::route
+pub trait FromSegment: Sized {
+    /// Parse `segment`, which is a part of `req`'s path.
+    ///
+    /// [`Error::Path`][1] should be returned when `segment` is not valid.
+    ///
+    /// [1]: crate::error::Error::Path
+    fn from_segment<T>(req: &T, segment: &str) -> Result<Self, Error>
+    where
+        T: PathReq + ?Sized;
+}
+
+impl<S> FromSegment for S
+where
+    S: FromStr,
+{
+    #[inline]
+    fn from_segment<T>(_: &T, segment: &str) -> Result<Self, Error>
+    where
+        T: PathReq + ?Sized,
+    {
+        segment.parse().map_err(|_| Error::Path)
+    }
+}
+
+/// A part of the request's path, captured without copying.
+///
+/// A `String` param or wildcard allocates for every request. `Capture` keeps
+/// a reference counted handle to the request's path instead, when the
+/// request provides one through [`PathReq::shared_path`]. Otherwise, the
+/// segment is copied.
+///
+/// ```ignore
+/// #[route(ANY: "proxy" / path*)]
+/// async fn proxy(req: Request<Body>, path: Capture) -> Result<Response<Body>, Error> {
+///     forward(req, &path).await
+/// }
+/// ```
+///
+/// Note that application code cannot construct this struct directly. This is
+/// exported for type annotation only.
+#[derive(Clone)]
+pub struct Capture {
+    inner: Inner,
+}
+
+#[derive(Clone)]
+enum Inner {
+    Shared(PathAndQuery, Range<usize>),
+    Owned(String),
+}
+
+impl Capture {
+    /// Get the captured string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        match &self.inner {
+            Inner::Shared(path, range) => &path.as_str()[range.clone()],
+            Inner::Owned(segment) => segment.as_str(),
+        }
+    }
+
+    /// Wether the capture shares the request's path instead of copying it.
+    #[inline]
+    pub fn is_shared(&self) -> bool {
+        matches!(self.inner, Inner::Shared(..))
+    }
+}
+
+impl FromSegment for Capture {
+    fn from_segment<T>(req: &T, segment: &str) -> Result<Self, Error>
+    where
+        T: PathReq + ?Sized,
+    {
+        let inner = match (req.shared_path(), offset(req.path(), segment)) {
+            // The shared path must agree with `PathReq::path`, since the range
+            // is computed from the latter.
+            (Some(path), Some(range)) if path.as_str().get(range.clone()) == Some(segment) => {
+                Inner::Shared(path, range)
+            }
+            _ => Inner::Owned(segment.to_string()),
+        };
+        Ok(Capture { inner })
+    }
+}
+
+/// Get the range of `segment` in `path`, if `segment` is a slice of `path`.
+fn offset(path: &str, segment: &str) -> Option<Range<usize>> {
+    let start = (segment.as_ptr() as usize).checked_sub(path.as_ptr() as usize)?;
+    let end = start + segment.len();
+    match end <= path.len() {
+        true => Some(start..end),
+        false => None,
+    }
+}
+
+impl Deref for Capture {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Capture {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for Capture {
+    #[inline]
+    fn eq(&self, other: &Capture) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Capture {}
+
+impl PartialEq<str> for Capture {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Capture {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl std::fmt::Debug for Capture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl std::fmt::Display for Capture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Request;
+
+    use super::Capture;
+    use super::FromSegment;
+
+    use crate::error::Error;
+    use crate::request::PathReq;
+
+    #[test]
+    fn from_str() {
+        let req = "/user/7";
+        assert_eq!(i32::from_segment(&req, &req.path()[6..]), Ok(7));
+        assert_eq!(i32::from_segment(&req, "seven"), Err(Error::Path));
+    }
+
+    #[test]
+    fn shared() {
+        let req = Request::builder().uri("/proxy/a/b?q").body(()).unwrap();
+        let capture = Capture::from_segment(&req, &req.path()[7..]).unwrap();
+        assert!(capture.is_shared());
+        assert_eq!(capture, "a/b");
+
+        // not a slice of the path.
+        let capture = Capture::from_segment(&req, "a/b").unwrap();
+        assert!(!capture.is_shared());
+        assert_eq!(capture, "a/b");
+
+        let req = "/proxy/a/b";
+        let capture = Capture::from_segment(&req, &req.path()[7..]).unwrap();
+        assert!(!capture.is_shared());
+        assert_eq!(capture, "a/b");
+    }
+}
@@ -0,0 +1,152 @@
+use std::task::Context;
+use std::task::Poll;
+
+use tower_service::Service;
+
+use crate::error::Error;
+use crate::extract::FromRequestParts;
+use crate::extract::Rejection;
+use crate::request::ExtensionReq;
+use crate::route::Route;
+
+/// Provide shared state to routes.
+///
+/// The state is cloned into every request's extensions before the request is
+/// passed to the inner route, where [`State`] extracts it again.
+///
+/// Note that application code cannot construct this struct directly. This is
+/// exported for type annotation only.
+#[derive(Clone, Copy, Debug)]
+pub struct WithState<R, S> {
+    inner: R,
+    state: S,
+}
+
+impl<R, S> WithState<R, S> {
+    #[inline]
+    pub(crate) fn new(inner: R, state: S) -> WithState<R, S> {
+        WithState { inner, state }
+    }
+}
+
+impl<R, S, T> Service<T> for WithState<R, S>
+where
+    R: Service<T>,
+    S: Clone + Send + Sync + 'static,
+    T: ExtensionReq,
+{
+    type Response = R::Response;
+
+    type Error = R::Error;
+
+    type Future = R::Future;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    #[inline]
+    fn call(&mut self, mut req: T) -> Self::Future {
+        req.extensions_mut().insert(self.state.clone());
+        self.inner.call(req)
+    }
+}
+
+impl<R, S, T> Route<T> for WithState<R, S>
+where
+    R: Route<T>,
+    S: Clone + Send + Sync + 'static,
+    T: ExtensionReq,
+{
+    type Param = R::Param;
+
+    #[inline]
+    fn call_with_param(&mut self, mut req: T, param: Self::Param) -> Self::Future {
+        req.extensions_mut().insert(self.state.clone());
+        self.inner.call_with_param(req, param)
+    }
+
+    #[inline]
+    fn param(&self, req: &T) -> Result<Self::Param, Error> {
+        self.inner.param(req)
+    }
+}
+
+/// Extract the state given to [`Router::with_state`][1].
+///
+/// [1]: crate::router::Router::with_state
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct State<S>(pub S);
+
+impl<T, S> FromRequestParts<T> for State<S>
+where
+    T: ExtensionReq,
+    S: Clone + Send + Sync + 'static,
+{
+    type Rejection = Rejection;
+
+    #[inline]
+    fn from_request_parts(req: &T) -> Result<Self, Self::Rejection> {
+        req.extensions()
+            .get::<S>()
+            .cloned()
+            .map(State)
+            .ok_or(Rejection::MissingState(std::any::type_name::<S>()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    use http::Request;
+    use http::Response;
+    use http::StatusCode;
+
+    use super::State;
+    use crate::exec::run;
+    use crate::macros::param;
+    use crate::router::Router;
+
+    param!(Home, GET, "/");
+    param!(Other, GET, "/other");
+
+    async fn home(State(count): State<Arc<AtomicUsize>>, _: Request<()>, _: Home) -> String {
+        count.fetch_add(1, Ordering::SeqCst).to_string()
+    }
+
+    async fn other(State(name): State<&'static str>, _: Request<()>, _: Other) -> String {
+        name.to_string()
+    }
+
+    fn req(path: &'static str) -> Request<()> {
+        Request::builder()
+            .method(http::Method::GET)
+            .uri(http::Uri::from_static(path))
+            .body(())
+            .unwrap()
+    }
+
+    #[test]
+    fn test() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let router = Router::handler(home).with_state(count.clone());
+
+        let res: Response<String> = run(router.clone(), req("/")).unwrap();
+        assert_eq!(res.body(), "0");
+        let res: Response<String> = run(router.clone(), req("/")).unwrap();
+        assert_eq!(res.body(), "1");
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+
+        let router = router.handle(other).with_state("other");
+        let res: Response<String> = run(router.clone(), req("/other")).unwrap();
+        assert_eq!(res.body(), "other");
+
+        let router = Router::handler(other);
+        let res: Response<String> = run(router, req("/other")).unwrap();
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,296 @@
+use std::future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::task::Context;
+use std::task::Poll;
+
+use tower_service::Service;
+
+use crate::boxed;
+use crate::boxed::BoxRoute;
+use crate::boxed::BoxRouter;
+use crate::error::Error;
+use crate::param;
+use crate::priority::Priority;
+use crate::route::Route;
+use crate::router::Router;
+
+/// A router that can be replaced while it is in use.
+///
+/// Every clone of a swap router shares the same routing table. Storing a new
+/// router with [`SwapRouter::store`] affects every clone, but requests that
+/// are already in-flight finish on the router they started with.
+///
+/// ```ignore
+/// let swap = SwapRouter::new(Router::new(index).boxed());
+/// let reload = swap.clone();
+///
+/// // on configuration reload:
+/// reload.store(Router::new(index).route(feature).boxed());
+/// ```
+///
+/// Matching a request shares the router, which is only cloned once the
+/// request is served, then polled for readiness before being called. See
+/// [`SwapRouter::router`] to combine it with other routes.
+pub struct SwapRouter<T, U, E> {
+    current: Arc<RwLock<Arc<BoxRoute<T, U, E>>>>,
+}
+
+impl<T, U, E> SwapRouter<T, U, E> {
+    /// Create a swap router with the initial router.
+    #[inline]
+    pub fn new(router: BoxRouter<T, U, E>) -> SwapRouter<T, U, E> {
+        SwapRouter {
+            current: Arc::new(RwLock::new(Arc::new(router.into_inner()))),
+        }
+    }
+
+    /// Replace the router, returning the previous one.
+    pub fn store(&self, router: BoxRouter<T, U, E>) -> BoxRouter<T, U, E> {
+        let mut current = self.current.write().unwrap_or_else(PoisonError::into_inner);
+        let previous = std::mem::replace(&mut *current, Arc::new(router.into_inner()));
+        drop(current);
+        BoxRouter::from_inner(Arc::try_unwrap(previous).unwrap_or_else(|route| (*route).clone()))
+    }
+
+    /// Get a copy of the current router.
+    #[inline]
+    pub fn load(&self) -> BoxRouter<T, U, E> {
+        BoxRouter::from_inner((*self.snapshot()).clone())
+    }
+
+    /// Get a router that routes with this swap router.
+    ///
+    /// The router shares the routing table with this swap router, so it can
+    /// be combined with other routes and served while the routing table is
+    /// being replaced.
+    #[inline]
+    pub fn router(&self) -> Router<SwapRouter<T, U, E>> {
+        Router::from_inner(self.clone())
+    }
+
+    fn snapshot(&self) -> Arc<BoxRoute<T, U, E>> {
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl<T, U, E> Clone for SwapRouter<T, U, E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        SwapRouter {
+            current: self.current.clone(),
+        }
+    }
+}
+
+impl<T, U, E> std::fmt::Debug for SwapRouter<T, U, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwapRouter").finish_non_exhaustive()
+    }
+}
+
+impl<T, U, E> Service<T> for SwapRouter<T, U, E>
+where
+    E: From<Error>,
+{
+    type Response = U;
+
+    type Error = E;
+
+    type Future = Future<T, U, E>;
+
+    #[inline]
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: T) -> Self::Future {
+        match self.param(&req) {
+            Err(err) => Future::Done(Some(Err(err.into()))),
+            Ok(param) => self.call_with_param(req, param),
+        }
+    }
+}
+
+impl<T, U, E> Route<T> for SwapRouter<T, U, E>
+where
+    E: From<Error>,
+{
+    type Param = Param<T, U, E>;
+
+    #[inline]
+    fn call_with_param(&mut self, req: T, param: Self::Param) -> Self::Future {
+        let route = (*param.route).clone();
+        Future::NotReady(Some((route, req, param.param)))
+    }
+
+    /// The param holds the router that matched, so the request is called on
+    /// the same router even if another one is stored in the meantime.
+    fn param(&self, req: &T) -> Result<Self::Param, Error> {
+        let route = self.snapshot();
+        let param = route.param(req)?;
+        Ok(Param { route, param })
+    }
+}
+
+pub struct Param<T, U, E> {
+    route: Arc<BoxRoute<T, U, E>>,
+    param: boxed::Param,
+}
+
+impl<T, U, E> std::fmt::Debug for Param<T, U, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Param").finish_non_exhaustive()
+    }
+}
+
+impl<T, U, E> param::Param<T> for Param<T, U, E> {
+    #[inline]
+    fn from_request(_: &T) -> Result<Self, Error> {
+        panic!("BUG: swap router should call param from Route trait.");
+    }
+
+    #[inline]
+    fn priority(&self) -> Priority {
+        param::Param::<T>::priority(&self.param)
+    }
+}
+
+/// Wait for the matched router to be ready, then call it.
+#[pin_project::pin_project(project = ProjectedFuture)]
+pub enum Future<T, U, E> {
+    NotReady(Option<(BoxRoute<T, U, E>, T, boxed::Param)>),
+    Called(#[pin] boxed::BoxFuture<U, E>),
+    Done(Option<Result<U, E>>),
+}
+
+impl<T, U, E> std::fmt::Debug for Future<T, U, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Future::NotReady(..) => f.debug_tuple("NotReady").finish(),
+            Future::Called(..) => f.debug_tuple("Called").finish(),
+            Future::Done(..) => f.debug_tuple("Done").finish(),
+        }
+    }
+}
+
+impl<T, U, E> future::Future for Future<T, U, E>
+where
+    E: From<Error>,
+{
+    type Output = Result<U, E>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            match self.as_mut().project() {
+                ProjectedFuture::NotReady(state) => {
+                    let (route, _, _) = match state {
+                        None => unreachable!("polled after ready"),
+                        Some(state) => state,
+                    };
+                    match route.poll_ready(cx) {
+                        Poll::Pending => return Poll::Pending,
+                        Poll::Ready(Err(err)) => {
+                            state.take();
+                            return Poll::Ready(Err(err));
+                        }
+                        Poll::Ready(Ok(())) => {
+                            let (mut route, req, param) = match state.take() {
+                                None => unreachable!("polled after ready"),
+                                Some(state) => state,
+                            };
+                            let fut = route.call_with_param(req, param);
+                            self.set(Future::Called(fut));
+                        }
+                    }
+                }
+                ProjectedFuture::Called(fut) => return fut.poll(cx),
+                ProjectedFuture::Done(out) => match out.take() {
+                    None => unreachable!("polled after ready"),
+                    Some(out) => return Poll::Ready(out),
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Request;
+
+    use super::SwapRouter;
+
+    use crate::error::Error;
+    use crate::exec::oneshot;
+    use crate::exec::run;
+    use crate::macros::param;
+    use crate::route::Route;
+    use crate::router::Router;
+
+    param!(Home, GET, "/");
+    param!(Feature, GET, "/feature");
+
+    async fn home(_: Request<()>, _: Home) -> Result<&'static str, Error> {
+        Ok("home")
+    }
+
+    async fn home2(_: Request<()>, _: Home) -> Result<&'static str, Error> {
+        Ok("home2")
+    }
+
+    async fn feature(_: Request<()>, _: Feature) -> Result<&'static str, Error> {
+        Ok("feature")
+    }
+
+    fn req(path: &'static str) -> Request<()> {
+        Request::builder()
+            .method(http::Method::GET)
+            .uri(http::Uri::from_static(path))
+            .body(())
+            .unwrap()
+    }
+
+    #[test]
+    fn test() {
+        let swap = SwapRouter::new(Router::new(home).boxed());
+        let router = swap.router();
+
+        let res = run(router.clone(), req("/"));
+        assert_eq!(res, Ok("home"));
+
+        let res = run(router.clone(), req("/feature"));
+        assert_eq!(res, Err(Error::Path));
+
+        swap.store(Router::new(home).route(feature).boxed());
+
+        let res = run(router.clone(), req("/feature"));
+        assert_eq!(res, Ok("feature"));
+
+        let res = run(swap.load(), req("/feature"));
+        assert_eq!(res, Ok("feature"));
+    }
+
+    #[test]
+    fn in_flight() {
+        let mut swap = SwapRouter::new(Router::new(home).boxed());
+
+        let param = swap.param(&req("/")).unwrap();
+        let fut = swap.call_with_param(req("/"), param);
+        let previous = swap.store(Router::new(home2).boxed());
+
+        assert_eq!(oneshot(fut), Ok("home"));
+        assert_eq!(run(swap, req("/")), Ok("home2"));
+        assert_eq!(run(previous, req("/")), Ok("home"));
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert<T: Send + Sync>() {}
+        assert::<SwapRouter<Request<()>, &'static str, Error>>();
+    }
+}
@@ -25,6 +25,7 @@ impl<T, U> Void<T, U> {
 
 impl<T, U> Copy for Void<T, U> {}
 
+#[allow(clippy::non_canonical_clone_impl)]
 impl<T, U> Clone for Void<T, U> {
     fn clone(&self) -> Self {
         Void(self.0, self.1)
@@ -1,11 +1,26 @@
 use std::future;
-use std::marker::PhantomData;
 use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
 
 use tower_service::Service;
 
+use crate::error::Error;
+use crate::future::Maybe;
+use crate::future::Oneshot;
+use crate::route::Route;
+
+/// A middleware that transforms the request of a route.
+///
+/// The inner route is matched first, so the middleware only runs on requests
+/// that the inner route serves, and a request for a missing route is not
+/// rejected by the middleware.
+///
+/// The inner route is cloned for every request and only called once the clone
+/// is ready, after the middleware has finished.
+///
+/// Note that application code cannot construct this struct directly. This is
+/// exported for type annotation only.
 #[derive(Clone, Copy, Debug)]
 pub struct With<S, F> {
     inner: S,
@@ -15,7 +30,7 @@ pub struct With<S, F> {
 impl<S, F> With<S, F> {
     pub(crate) fn new<T, Fut>(inner: S, func: F) -> With<S, F>
     where
-        S: Clone + Service<T>,
+        S: Clone + Route<T>,
         F: FnMut(T) -> Fut,
         Fut: future::Future<Output = Result<T, S::Error>>,
     {
@@ -24,6 +39,77 @@ impl<S, F> With<S, F> {
 }
 
 impl<T, S, F, Fut> Service<T> for With<S, F>
+where
+    S: Clone + Route<T>,
+    S::Error: From<Error>,
+    F: FnMut(T) -> Fut,
+    Fut: future::Future<Output = Result<T, S::Error>>,
+{
+    type Response = S::Response;
+
+    type Error = S::Error;
+
+    type Future = Maybe<Future<Fut, S, T>, Result<S::Response, S::Error>>;
+
+    #[inline]
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: T) -> Self::Future {
+        match self.param(&req) {
+            Ok(param) => self.call_with_param(req, param),
+            Err(err) => Maybe::ready(Err(err.into())),
+        }
+    }
+}
+
+impl<T, S, F, Fut> Route<T> for With<S, F>
+where
+    S: Clone + Route<T>,
+    S::Error: From<Error>,
+    F: FnMut(T) -> Fut,
+    Fut: future::Future<Output = Result<T, S::Error>>,
+{
+    type Param = S::Param;
+
+    /// The param is dropped: the inner route matches the request again after
+    /// the middleware, since the middleware may change it.
+    fn call_with_param(&mut self, req: T, _: Self::Param) -> Self::Future {
+        Maybe::Future(Future::new((self.func)(req), self.inner.clone()))
+    }
+
+    fn param(&self, req: &T) -> Result<Self::Param, Error> {
+        self.inner.param(req)
+    }
+}
+
+/// A middleware that transforms the request of a service.
+///
+/// Unlike [`With`], the inner service is not a route that can be matched
+/// first, so the middleware runs on every request, including the requests
+/// the inner service does not serve.
+///
+/// Note that application code cannot construct this struct directly. This is
+/// exported for type annotation only.
+#[derive(Clone, Copy, Debug)]
+pub struct WithService<S, F> {
+    inner: S,
+    func: F,
+}
+
+impl<S, F> WithService<S, F> {
+    pub(crate) fn new<T, Fut>(inner: S, func: F) -> WithService<S, F>
+    where
+        S: Clone + Service<T>,
+        F: FnMut(T) -> Fut,
+        Fut: future::Future<Output = Result<T, S::Error>>,
+    {
+        WithService { inner, func }
+    }
+}
+
+impl<T, S, F, Fut> Service<T> for WithService<S, F>
 where
     S: Clone + Service<T>,
     F: FnMut(T) -> Fut,
@@ -35,45 +121,53 @@ where
 
     type Future = Future<Fut, S, T>;
 
+    #[inline]
     fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         Poll::Ready(Ok(()))
     }
 
-    #[allow(unused_must_use)]
-    #[allow(clippy::todo)]
     fn call(&mut self, req: T) -> Self::Future {
         Future::new((self.func)(req), self.inner.clone())
     }
 }
 
-#[derive(Debug)]
-#[pin_project::pin_project]
-pub struct Future<F, S, T> {
-    #[pin]
-    fut: F,
-    svc: S,
-
-    req: PhantomData<T>,
+/// The middleware future, then the inner service future.
+#[pin_project::pin_project(project = ProjectedFuture)]
+pub enum Future<F, S, T>
+where
+    S: Service<T>,
+{
+    Middleware {
+        #[pin]
+        fut: F,
+        svc: Option<S>,
+    },
+    Inner {
+        #[pin]
+        fut: Oneshot<S, T>,
+    },
 }
 
-impl<F, S, T> Future<F, S, T> {
+impl<F, S, T> Future<F, S, T>
+where
+    S: Service<T>,
+{
     fn new(fut: F, svc: S) -> Future<F, S, T> {
-        Future {
+        Future::Middleware {
             fut,
-            svc,
-            req: PhantomData,
+            svc: Some(svc),
         }
     }
 }
 
-impl<F: Copy, S: Copy, T> Copy for Future<F, S, T> {}
-
-impl<F: Clone, S: Clone, T> Clone for Future<F, S, T> {
-    fn clone(&self) -> Self {
-        Future {
-            fut: self.fut.clone(),
-            svc: self.svc.clone(),
-            req: self.req,
+impl<F, S, T> std::fmt::Debug for Future<F, S, T>
+where
+    S: Service<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Future::Middleware { .. } => f.debug_struct("Middleware").finish(),
+            Future::Inner { .. } => f.debug_struct("Inner").finish(),
         }
     }
 }
@@ -85,19 +179,151 @@ where
 {
     type Output = Result<U, E>;
 
-    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let this = self.project();
-        match this.fut.poll(cx) {
-            Poll::Pending => Poll::Pending,
-            Poll::Ready(out) => match out {
-                Err(err) => Poll::Ready(Err(err)),
-                Ok(req) => {
-                    // let mut res = this.svc.call(req);
-                    // let mut res = unsafe { Pin::new_unchecked(&mut res) };
-                    let mut res = Box::pin(this.svc.call(req));
-                    res.as_mut().poll(cx)
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            match self.as_mut().project() {
+                ProjectedFuture::Middleware { fut, svc } => {
+                    let req = match fut.poll(cx) {
+                        Poll::Pending => return Poll::Pending,
+                        Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                        Poll::Ready(Ok(req)) => req,
+                    };
+                    let svc = match svc.take() {
+                        None => unreachable!("polled after ready"),
+                        Some(svc) => svc,
+                    };
+                    let fut = Oneshot::new(svc, req);
+                    self.set(Future::Inner { fut });
                 }
-            },
+                ProjectedFuture::Inner { fut } => return fut.poll(cx),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Request;
+
+    use crate::error::Error;
+    use crate::exec::run;
+    use crate::exec::yield_now;
+    use crate::exec::Gate;
+    use crate::macros::param;
+    use crate::router::Router;
+
+    param!(Home, GET, "/");
+    param!(Private, GET, "/private");
+    param!(Slow, GET, "/slow");
+
+    #[derive(Debug, PartialEq)]
+    enum Failure {
+        Routing(Error),
+        Unauthorized,
+    }
+
+    impl From<Error> for Failure {
+        fn from(err: Error) -> Self {
+            Failure::Routing(err)
+        }
+    }
+
+    async fn home(_: Request<()>, _: Home) -> Result<&'static str, Failure> {
+        Ok("home")
+    }
+
+    async fn private(_: Request<()>, _: Private) -> Result<&'static str, Failure> {
+        Ok("private")
+    }
+
+    async fn slow(_: Request<()>, _: Slow) -> Result<&'static str, Failure> {
+        yield_now().await;
+        yield_now().await;
+        Ok("slow")
+    }
+
+    async fn guard(req: Request<()>) -> Result<Request<()>, Failure> {
+        yield_now().await;
+        match req.headers().contains_key("user") {
+            true => Ok(req),
+            false => Err(Failure::Unauthorized),
         }
     }
+
+    fn req(path: &'static str) -> Request<()> {
+        Request::builder()
+            .method(http::Method::GET)
+            .uri(http::Uri::from_static(path))
+            .body(())
+            .unwrap()
+    }
+
+    #[test]
+    fn test() {
+        let router = Router::new(home).merge(Router::new(private).with(guard));
+
+        let res = run(router, req("/"));
+        assert_eq!(res, Ok("home"));
+
+        let res = run(router, req("/private"));
+        assert_eq!(res, Err(Failure::Unauthorized));
+
+        let mut authorized = req("/private");
+        authorized
+            .headers_mut()
+            .insert("user", "someone".try_into().unwrap());
+        let res = run(router, authorized);
+        assert_eq!(res, Ok("private"));
+
+        let res = run(router, req("/nowhere"));
+        assert_eq!(res, Err(Failure::Routing(Error::Path)));
+    }
+
+    #[test]
+    fn service() {
+        // `with` matches first.
+        let router = Router::new(private).with(guard);
+        let res = run(router, req("/nowhere"));
+        assert_eq!(res, Err(Failure::Routing(Error::Path)));
+
+        let res = run(router, req("/private"));
+        assert_eq!(res, Err(Failure::Unauthorized));
+
+        // `with_service` runs the middleware on every request.
+        let router = Router::new(private).with_service(guard);
+        let res = run(router, req("/nowhere"));
+        assert_eq!(res, Err(Failure::Unauthorized));
+
+        let mut authorized = req("/private");
+        authorized
+            .headers_mut()
+            .insert("user", "someone".try_into().unwrap());
+        let res = run(router, authorized);
+        assert_eq!(res, Ok("private"));
+    }
+
+    #[test]
+    fn pending() {
+        let router = Router::new(slow).with(guard);
+
+        let mut authorized = req("/slow");
+        authorized
+            .headers_mut()
+            .insert("user", "someone".try_into().unwrap());
+        let res = run(router, authorized);
+        assert_eq!(res, Ok("slow"));
+    }
+
+    #[test]
+    fn ready() {
+        let gated = Gate::new(Router::new(private));
+        let router = Router::new(home).mount("/gated", gated).with(guard);
+
+        let mut authorized = req("/gated/private");
+        authorized
+            .headers_mut()
+            .insert("user", "someone".try_into().unwrap());
+        let res = run(router, authorized);
+        assert_eq!(res, Ok("private"));
+    }
 }
@@ -1,10 +1,13 @@
 use std::future::Future;
 
+use http::header::LOCATION;
 use http::Method;
 use http::Request;
 use http::Response;
+use http::StatusCode;
 use http::Uri;
 use tackt::route;
+use tackt::Capture;
 use tackt::Param;
 use tower_service::Service;
 
@@ -36,6 +39,18 @@ fn test() {
     );
 
     let res = oneshot(router.call(request(Method::GET, "/protected")));
+    assert_eq!(
+        res.map(Response::into_body),
+        Err(Error::Routing(tackt::Error::Path))
+    );
+
+    let res = oneshot(router.call(request(Method::GET, "/protected/nowhere")));
+    assert_eq!(
+        res.map(Response::into_body),
+        Err(Error::Routing(tackt::Error::Path))
+    );
+
+    let res = oneshot(router.call(request(Method::GET, "/protected/")));
     assert_eq!(res.map(Response::into_body), Err(Error::Unauthorized));
 
     let mut req = request(Method::GET, "/protected/");
@@ -45,6 +60,320 @@ fn test() {
     assert_eq!(res.map(Response::into_body), Ok("someone".to_string()));
 }
 
+#[test]
+#[cfg(feature = "response")]
+fn handler() {
+    let router = tackt::Router::handler(item).respond();
+
+    let mut req = request(Method::GET, "/item/1");
+    req.headers_mut()
+        .insert("user", "someone".try_into().unwrap());
+    let res: Response<String> = oneshot(router.clone().call(req)).unwrap();
+    assert_eq!(res.status(), http::StatusCode::OK);
+    assert_eq!(res.into_body(), "item 1 for someone");
+
+    let res = oneshot(router.clone().call(request(Method::GET, "/item/x"))).unwrap();
+    assert_eq!(res.status(), http::StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn enumeration() {
+    let mut router = tackt::Router::new(users);
+
+    let res = oneshot(router.call(request(Method::GET, "/users")));
+    assert_eq!(res.map(Response::into_body), Ok("list".to_string()));
+
+    let res = oneshot(router.call(request(Method::GET, "/users/1")));
+    assert_eq!(res.map(Response::into_body), Ok("show 1".to_string()));
+
+    let res = oneshot(router.call(request(Method::DELETE, "/users/1")));
+    assert_eq!(res.map(Response::into_body), Ok("delete 1".to_string()));
+
+    let res = oneshot(router.call(request(Method::GET, "/users/1/files/a/b")));
+    assert_eq!(res.map(Response::into_body), Ok("file 1 a/b".to_string()));
+
+    // errors of the variants are combined like `Or` does.
+    let res = oneshot(router.call(request(Method::POST, "/users/1")));
+    assert_eq!(
+        res.map(Response::into_body),
+        Err(Error::Routing(tackt::Error::Path))
+    );
+
+    let res = oneshot(router.call(request(Method::GET, "/users/x")));
+    assert_eq!(
+        res.map(Response::into_body),
+        Err(Error::Routing(tackt::Error::Path))
+    );
+}
+
+#[test]
+fn tuple_and_unit() {
+    use tackt::Param;
+
+    let res = Pair::from_request(&request(Method::GET, "/pair/1/a"));
+    assert_eq!(
+        res.map(|Pair(id, name)| (id, name)),
+        Ok((1, "a".to_string()))
+    );
+
+    let res = Health::from_request(&request(Method::GET, "/health"));
+    assert!(res.is_ok());
+
+    let res = Health::from_request(&request(Method::POST, "/health"));
+    assert!(matches!(res, Err(tackt::Error::Method)));
+
+    let res = Lookup::from_request(&request(Method::GET, "/lookup/7"));
+    assert!(matches!(res, Ok(Lookup::ById(7))));
+
+    let res = Lookup::from_request(&request(Method::GET, "/lookup"));
+    assert!(matches!(res, Ok(Lookup::All)));
+}
+
+#[test]
+fn generics() {
+    use tackt::Param;
+
+    let res = ById::<u8>::from_request(&request(Method::GET, "/by-id/7"));
+    assert_eq!(res.map(|param| param.id), Ok(7));
+
+    let res = ById::<u8>::from_request(&request(Method::GET, "/by-id/700"));
+    assert!(matches!(res, Err(tackt::Error::Path)));
+
+    let res = Wrap::<String>::from_request(&request(Method::GET, "/wrap/x"));
+    assert_eq!(res.map(|Wrap(value)| value), Ok("x".to_string()));
+
+    let res = Either::<u8, bool>::from_request(&request(Method::GET, "/either/true"));
+    assert!(matches!(res, Ok(Either::Right(true))));
+
+    // ranked by priority like `Or`, not by declaration order.
+    let res = Account::from_request(&request(Method::GET, "/account/me"));
+    assert!(matches!(res, Ok(Account::Me)));
+
+    let res = Account::from_request(&request(Method::GET, "/account/john"));
+    assert!(matches!(res, Ok(Account::Name { name }) if name == "john"));
+}
+
+#[test]
+fn controller() {
+    let mut router = Users {
+        name: "users".to_string(),
+    }
+    .router()
+    .route(home);
+
+    let res = oneshot(router.call(request(Method::GET, "/")));
+    assert_eq!(res.map(Response::into_body), Ok("home".to_string()));
+
+    let res = oneshot(router.call(request(Method::GET, "/users")));
+    assert_eq!(res.map(Response::into_body), Ok("users".to_string()));
+
+    let res = oneshot(router.call(request(Method::GET, "/users/1/a")));
+    assert_eq!(res.map(Response::into_body), Ok("users 1 a".to_string()));
+
+    let res = oneshot(router.call(request(Method::GET, "/users/x/a")));
+    assert_eq!(
+        res.map(Response::into_body),
+        Err(Error::Routing(tackt::Error::Path))
+    );
+
+    // the return type may be an alias of `Result`.
+    let mut router = Status.router();
+    let res = oneshot(router.call(request(Method::GET, "/status")));
+    assert_eq!(res.map(Response::into_body), Ok("ok".to_string()));
+}
+
+#[test]
+fn prefix() {
+    let mut router = tackt::Router::new(api::user)
+        .route(api::create)
+        .route(api::find)
+        .route(api::admin::stats)
+        .merge(Versions.router());
+
+    let res = oneshot(router.call(request(Method::GET, "/api/v1/user/1")));
+    assert_eq!(res.map(Response::into_body), Ok("user 1".to_string()));
+
+    let res = oneshot(router.call(request(Method::POST, "/api/v1/user")));
+    assert_eq!(res.map(Response::into_body), Ok("create".to_string()));
+
+    let res = oneshot(router.call(request(Method::GET, "/api/v1/find/a")));
+    assert_eq!(res.map(Response::into_body), Ok("find a".to_string()));
+
+    let res = oneshot(router.call(request(Method::GET, "/api/v1/admin/stats")));
+    assert_eq!(res.map(Response::into_body), Ok("stats".to_string()));
+
+    let res = oneshot(router.call(request(Method::GET, "/api/versions")));
+    assert_eq!(res.map(Response::into_body), Ok("v1".to_string()));
+
+    let res = oneshot(router.call(request(Method::GET, "/user/1")));
+    assert_eq!(
+        res.map(Response::into_body),
+        Err(Error::Routing(tackt::Error::Path))
+    );
+}
+
+#[test]
+fn extension_method() {
+    let mut router = tackt::Router::new(dav).route(proxy);
+    let propfind = Method::from_bytes(b"PROPFIND").unwrap();
+    let purge = Method::from_bytes(b"PURGE").unwrap();
+
+    let res = oneshot(router.call(request(propfind, "/dav/a/b")));
+    assert_eq!(res.map(Response::into_body), Ok("PROPFIND a/b".to_string()));
+
+    let res = oneshot(tackt::Router::new(dav).call(request(purge.clone(), "/dav/a/b")));
+    assert_eq!(
+        res.map(Response::into_body),
+        Err(Error::Routing(tackt::Error::Method))
+    );
+
+    let res = oneshot(router.call(request(purge, "/proxy/a")));
+    assert_eq!(res.map(Response::into_body), Ok("PURGE a".to_string()));
+}
+
+#[test]
+fn slash_literal() {
+    let mut router = tackt::Router::new(api_user);
+
+    let res = oneshot(router.call(request(Method::GET, "/api/v1/users/7")));
+    assert_eq!(res.map(Response::into_body), Ok("user 7".to_string()));
+
+    let res = oneshot(router.call(request(Method::GET, "/api/v1users/7")));
+    assert_eq!(
+        res.map(Response::into_body),
+        Err(Error::Routing(tackt::Error::Path))
+    );
+
+    let res = oneshot(router.call(request(Method::GET, "/api/v1/users")));
+    assert_eq!(
+        res.map(Response::into_body),
+        Err(Error::Routing(tackt::Error::Path))
+    );
+}
+
+#[test]
+fn capture() {
+    let mut router = tackt::Router::new(files);
+
+    let res = oneshot(router.call(request(Method::GET, "/files/7/a/b.txt")));
+    assert_eq!(
+        res.map(Response::into_body),
+        Ok("7 a/b.txt shared".to_string())
+    );
+
+    let res = oneshot(router.call(request(Method::GET, "/files/x/a")));
+    assert_eq!(
+        res.map(Response::into_body),
+        Err(Error::Routing(tackt::Error::Path))
+    );
+}
+
+#[test]
+fn priority() {
+    // registered from the least specific to the most specific.
+    let mut router = tackt::routes![profile_me, profile_id, profile_name, profile_rest];
+
+    let cases = [
+        ("/profile/me", "me"),
+        ("/profile/7", "id 7"),
+        ("/profile/john", "name john"),
+        ("/profile/john/posts", "rest john/posts"),
+    ];
+    for (path, body) in cases {
+        let res = oneshot(router.call(request(Method::GET, path)));
+        assert_eq!(res.map(Response::into_body), Ok(body.to_string()));
+    }
+
+    // an explicit priority wins over the specificity.
+    let mut router = tackt::Router::new(profile_me).route(profile_any);
+    let res = oneshot(router.call(request(Method::GET, "/profile/me")));
+    assert_eq!(res.map(Response::into_body), Ok("any me".to_string()));
+}
+
+#[test]
+fn exact_and_wildcard() {
+    let exact_first = tackt::Router::new(assets).route(assets_rest);
+    let wildcard_first = tackt::Router::new(assets_rest).route(assets);
+
+    for mut router in [exact_first.boxed(), wildcard_first.boxed()] {
+        let res = oneshot(router.call(request(Method::GET, "/assets")));
+        assert_eq!(res.map(Response::into_body), Ok("assets".to_string()));
+
+        let res = oneshot(router.call(request(Method::GET, "/assets/app.js")));
+        assert_eq!(res.map(Response::into_body), Ok("rest app.js".to_string()));
+    }
+}
+
+#[test]
+fn literal_with_option() {
+    let mut router = tackt::Router::new(health);
+
+    let res = oneshot(router.call(request(Method::GET, "/Health")));
+    assert_eq!(res.map(Response::into_body), Ok("health".to_string()));
+
+    let res = oneshot(router.call(request(Method::GET, "/")));
+    assert_eq!(
+        res.map(Response::into_body),
+        Err(Error::Routing(tackt::Error::Path))
+    );
+}
+
+#[test]
+fn ignore_case() {
+    let mut router = tackt::Router::new(legacy::login)
+        .route(legacy::user)
+        .route(sign_up);
+
+    let cases = [
+        ("/Legacy/Login", "login"),
+        ("/LEGACY/USER/John", "user John"),
+        ("/SIGN/UP", "sign up"),
+    ];
+    for (path, body) in cases {
+        let res = oneshot(router.call(request(Method::GET, path)));
+        assert_eq!(res.map(Response::into_body), Ok(body.to_string()));
+    }
+
+    let res = oneshot(router.call(request(Method::GET, "/Legacy/Logins")));
+    assert_eq!(
+        res.map(Response::into_body),
+        Err(Error::Routing(tackt::Error::Path))
+    );
+
+    let req = Plain {
+        method: Method::GET,
+        path: "/CAFÉ/Ann",
+    };
+    let cafe = Cafe::from_request(&req).map(|cafe| cafe.name);
+    assert_eq!(cafe, Ok("Ann".to_string()));
+
+    let req = Plain {
+        method: Method::GET,
+        path: "/cafe/Ann",
+    };
+    assert!(Cafe::from_request(&req).is_err());
+}
+
+#[test]
+fn redirect() {
+    let mut router = tackt::Router::new(profile_me).route(user).redirect(
+        "/profile/{id}",
+        "/user/{id}",
+        StatusCode::PERMANENT_REDIRECT,
+    );
+
+    let res = oneshot(router.call(request(Method::GET, "/profile/7?tab=posts"))).unwrap();
+    assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(res.headers()[LOCATION], "/user/7?tab=posts");
+
+    // the literal route is more specific than the redirect.
+    let res = oneshot(router.call(request(Method::GET, "/profile/me")));
+    assert_eq!(res.map(Response::into_body), Ok("me".to_string()));
+
+    let res = oneshot(router.call(request(Method::GET, "/user/7")));
+    assert_eq!(res.map(Response::into_body), Ok("user 7".to_string()));
+}
+
 fn respond<S: Into<String>>(body: S) -> Response<String> {
     Response::new(body.into())
 }
@@ -71,6 +400,13 @@ async fn user(_: Request<()>, id: i32) -> Result<Response<String>, Error> {
     Ok(respond(format!("user {}", id)))
 }
 
+#[cfg(feature = "response")]
+#[route(GET: "item" / id)]
+async fn item(headers: http::HeaderMap, _: Request<()>, id: i32) -> String {
+    let user = headers.get("user").and_then(|user| user.to_str().ok());
+    format!("item {} for {}", id, user.unwrap_or("nobody"))
+}
+
 #[derive(Param)]
 #[route(GET: "content" / id / name / path*)]
 struct Content {
@@ -86,6 +422,274 @@ async fn content(_: Request<()>, param: Content) -> Result<Response<String>, Err
     )))
 }
 
+#[derive(Param)]
+enum UserRoute {
+    #[route(GET: "users")]
+    List,
+    #[route(GET: "users" / id)]
+    Show { id: u64 },
+    #[route(DELETE: "users" / id)]
+    Delete { id: u64 },
+    #[route(GET: "users" / id / "files" / path*)]
+    File { id: u64, path: String },
+}
+
+async fn users(_: Request<()>, param: UserRoute) -> Result<Response<String>, Error> {
+    Ok(respond(match param {
+        UserRoute::List => "list".to_string(),
+        UserRoute::Show { id } => format!("show {id}"),
+        UserRoute::Delete { id } => format!("delete {id}"),
+        UserRoute::File { id, path } => format!("file {id} {path}"),
+    }))
+}
+
+#[derive(Param)]
+#[route(GET: "pair" / id / name)]
+struct Pair(u64, String);
+
+#[derive(Param)]
+#[route(GET: "health")]
+struct Health;
+
+#[derive(Param)]
+enum Lookup {
+    #[route(GET: "lookup")]
+    All,
+    #[route(GET: "lookup" / id)]
+    ById(u64),
+}
+
+#[derive(Param)]
+enum Account {
+    #[route(GET: "account" / name)]
+    Name { name: String },
+    #[route(GET: "account" / "me")]
+    Me,
+}
+
+#[derive(Param)]
+#[route(GET: "by-id" / id)]
+struct ById<Id: std::str::FromStr> {
+    id: Id,
+}
+
+#[derive(Param)]
+#[route(GET: "wrap" / value)]
+struct Wrap<T>(T)
+where
+    T: std::str::FromStr;
+
+#[derive(Param)]
+enum Either<L: std::str::FromStr, R: std::str::FromStr> {
+    #[route(GET: "either" / left)]
+    Left(L),
+    #[route(GET: "either" / right)]
+    Right(R),
+}
+
+struct Users {
+    name: String,
+}
+
+#[tackt::controller]
+impl Users {
+    #[route(GET: "users")]
+    async fn list(&self, _: Request<()>) -> Result<Response<String>, Error> {
+        Ok(respond(self.name.as_str()))
+    }
+
+    #[route(GET: "users" / id / name)]
+    async fn show(&self, _: Request<()>, id: u64, name: String) -> Result<Response<String>, Error> {
+        Ok(respond(self.describe(id, &name)))
+    }
+
+    fn describe(&self, id: u64, name: &str) -> String {
+        format!("{} {} {}", self.name, id, name)
+    }
+}
+
+#[tackt::route_prefix("api" / "v1")]
+mod api {
+    use http::Request;
+    use http::Response;
+    use tackt::get;
+    use tackt::post;
+    use tackt::Param;
+
+    use super::respond;
+    use super::Error;
+
+    #[get("user" / id)]
+    pub async fn user(_: Request<()>, id: u64) -> Result<Response<String>, Error> {
+        Ok(respond(format!("user {id}")))
+    }
+
+    #[post("user")]
+    pub async fn create(_: Request<()>) -> Result<Response<String>, Error> {
+        Ok(respond("create"))
+    }
+
+    #[derive(Param)]
+    #[route(GET: "find" / name)]
+    pub struct Find {
+        name: String,
+    }
+
+    pub async fn find(_: Request<()>, param: Find) -> Result<Response<String>, Error> {
+        Ok(respond(format!("find {}", param.name)))
+    }
+
+    #[tackt::route_prefix("admin")]
+    pub mod admin {
+        use http::Request;
+        use http::Response;
+        use tackt::get;
+
+        use super::respond;
+        use super::Error;
+
+        #[get("stats")]
+        pub async fn stats(_: Request<()>) -> Result<Response<String>, Error> {
+            Ok(respond("stats"))
+        }
+    }
+}
+
+#[tackt::route_prefix("legacy", ignore_ascii_case)]
+mod legacy {
+    use http::Request;
+    use http::Response;
+    use tackt::get;
+
+    use super::respond;
+    use super::Error;
+
+    #[get("login")]
+    pub async fn login(_: Request<()>) -> Result<Response<String>, Error> {
+        Ok(respond("login"))
+    }
+
+    #[get("user" / name)]
+    pub async fn user(_: Request<()>, name: String) -> Result<Response<String>, Error> {
+        Ok(respond(format!("user {name}")))
+    }
+}
+
+#[tackt::get("sign/up", ignore_ascii_case)]
+async fn sign_up(_: Request<()>) -> Result<Response<String>, Error> {
+    Ok(respond("sign up"))
+}
+
+/// A request that is not limited to ASCII.
+struct Plain {
+    method: Method,
+    path: &'static str,
+}
+
+impl tackt::PathReq for Plain {
+    fn path(&self) -> &str {
+        self.path
+    }
+}
+
+impl tackt::MethodReq for Plain {
+    fn method(&self) -> &Method {
+        &self.method
+    }
+}
+
+#[derive(Param)]
+#[route(GET: "café" / name, ignore_case)]
+struct Cafe {
+    name: String,
+}
+
+mod reply {
+    pub type Result<T> = std::result::Result<T, super::Error>;
+}
+
+struct Status;
+
+#[tackt::controller]
+impl Status {
+    #[route(GET: "status")]
+    async fn status(&self, _: Request<()>) -> reply::Result<Response<String>> {
+        Ok(respond("ok"))
+    }
+}
+
+struct Versions;
+
+#[tackt::controller]
+#[tackt::route_prefix("api")]
+impl Versions {
+    #[tackt::get("versions")]
+    async fn versions(&self, _: Request<()>) -> Result<Response<String>, Error> {
+        Ok(respond("v1"))
+    }
+}
+
+#[route("PROPFIND", "MKCOL": "dav" / path*)]
+async fn dav(req: Request<()>, path: String) -> Result<Response<String>, Error> {
+    Ok(respond(format!("{} {}", req.method(), path)))
+}
+
+#[route(ANY: "proxy" / path*)]
+async fn proxy(req: Request<()>, path: String) -> Result<Response<String>, Error> {
+    Ok(respond(format!("{} {}", req.method(), path)))
+}
+
+#[tackt::get("api/v1" / "users" / id)]
+async fn api_user(_: Request<()>, id: i32) -> Result<Response<String>, Error> {
+    Ok(respond(format!("user {id}")))
+}
+
+#[tackt::get("files" / id / path*)]
+async fn files(_: Request<()>, id: i32, path: Capture) -> Result<Response<String>, Error> {
+    let shared = if path.is_shared() { "shared" } else { "owned" };
+    Ok(respond(format!("{id} {path} {shared}")))
+}
+
+#[tackt::get("profile" / "me")]
+async fn profile_me(_: Request<()>) -> Result<Response<String>, Error> {
+    Ok(respond("me"))
+}
+
+#[tackt::get("profile" / id)]
+async fn profile_id(_: Request<()>, id: u32) -> Result<Response<String>, Error> {
+    Ok(respond(format!("id {id}")))
+}
+
+#[tackt::get("profile" / name)]
+async fn profile_name(_: Request<()>, name: String) -> Result<Response<String>, Error> {
+    Ok(respond(format!("name {name}")))
+}
+
+#[tackt::get("profile" / rest*)]
+async fn profile_rest(_: Request<()>, rest: Capture) -> Result<Response<String>, Error> {
+    Ok(respond(format!("rest {rest}")))
+}
+
+#[route("health", ignore_ascii_case)]
+async fn health(_: Request<()>) -> Result<Response<String>, Error> {
+    Ok(respond("health"))
+}
+
+#[tackt::get("assets")]
+async fn assets(_: Request<()>) -> Result<Response<String>, Error> {
+    Ok(respond("assets"))
+}
+
+#[tackt::get("assets" / rest*)]
+async fn assets_rest(_: Request<()>, rest: String) -> Result<Response<String>, Error> {
+    Ok(respond(format!("rest {rest}")))
+}
+
+#[tackt::get("profile" / rest*, priority = 1)]
+async fn profile_any(_: Request<()>, rest: String) -> Result<Response<String>, Error> {
+    Ok(respond(format!("any {rest}")))
+}
+
 #[route]
 async fn protected(req: Request<()>) -> Result<Response<String>, Error> {
     let user = req.headers().get("user").unwrap().to_str().unwrap();
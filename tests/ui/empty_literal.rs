@@ -0,0 +1,6 @@
+#[tackt::get("a" / "")]
+async fn handler(req: http::Request<()>) -> Result<http::Request<()>, tackt::Error> {
+    Ok(req)
+}
+
+fn main() {}
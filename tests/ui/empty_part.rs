@@ -0,0 +1,6 @@
+#[tackt::route(GET: "api//v1" / id)]
+async fn handler(req: http::Request<()>, id: String) -> Result<http::Request<()>, tackt::Error> {
+    Ok(req)
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+struct Users<S> {
+    name: S,
+}
+
+#[tackt::controller]
+impl<S: Send + Sync + 'static> Users<S> {
+    #[route(GET: "users")]
+    async fn list(&self, req: http::Request<()>) -> Result<http::Request<()>, tackt::Error> {
+        Ok(req)
+    }
+}
+
+fn main() {}
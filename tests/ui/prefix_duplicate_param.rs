@@ -0,0 +1,9 @@
+#[tackt::route_prefix("org" / id)]
+mod org {
+    #[tackt::get("user" / id)]
+    async fn user(req: http::Request<()>, id: String) -> Result<http::Request<()>, tackt::Error> {
+        Ok(req)
+    }
+}
+
+fn main() {}
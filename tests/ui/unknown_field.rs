@@ -0,0 +1,7 @@
+#[derive(tackt::Param)]
+#[route(GET: "user" / user_id)]
+struct User {
+    userid: String,
+}
+
+fn main() {}
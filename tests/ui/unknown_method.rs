@@ -0,0 +1,6 @@
+#[tackt::route(PURGE: "cache")]
+async fn handler(req: http::Request<()>) -> Result<http::Request<()>, tackt::Error> {
+    Ok(req)
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+#[tackt::get("user" / id, priorty = 1)]
+async fn handler(req: http::Request<()>, id: String) -> Result<http::Request<()>, tackt::Error> {
+    Ok(req)
+}
+
+fn main() {}